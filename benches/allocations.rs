@@ -0,0 +1,87 @@
+/*
+ * Heap allocations made by the block modes for 1 MB of data
+ *
+ * Run with `cargo bench --bench allocations`. Every call allocates its output once and processes
+ * the blocks in place, without further allocations per block.
+ */
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rc5_test::{Iv, Padding, Rc5};
+
+const KEY: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+];
+
+const IV: Iv = Iv::new([0xA5; 8]);
+
+const MB: usize = 1 << 20;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/**
+ * System allocator counting allocations and reallocations
+ */
+struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/**
+ * Number of allocations made by `f`, not counting those for dropping its result
+ */
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let result = f();
+    let count = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    drop(result);
+    count
+}
+
+fn main() {
+    let cipher = Rc5::new(&KEY).unwrap();
+    let plaintext = vec![0x5A; MB];
+    let ecb = cipher.encrypt_ecb(&plaintext, Padding::Pkcs7).unwrap();
+    let cbc = cipher.encrypt_cbc(IV, &plaintext, Padding::Pkcs7).unwrap();
+
+    let counts = [
+        (
+            "ecb encrypt",
+            allocations(|| cipher.encrypt_ecb(&plaintext, Padding::Pkcs7)),
+        ),
+        (
+            "ecb decrypt",
+            allocations(|| cipher.decrypt_ecb(&ecb, Padding::Pkcs7)),
+        ),
+        (
+            "cbc encrypt",
+            allocations(|| cipher.encrypt_cbc(IV, &plaintext, Padding::Pkcs7)),
+        ),
+        (
+            "cbc decrypt",
+            allocations(|| cipher.decrypt_cbc(IV, &cbc, Padding::Pkcs7)),
+        ),
+    ];
+
+    for (name, count) in counts.iter() {
+        println!("{:<12} {} allocation(s) for {} blocks", name, count, MB / 8);
+        assert_eq!(*count, 1, "{} allocates per block", name);
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * Throughput of single blocks, ECB over 1 MB and the key schedule
+ *
+ * Run with `cargo bench`, throughput is reported per second.
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use rc5_test::{Padding, Rc5, Rc5Default};
+
+const KEY: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+];
+
+const MB: usize = 1 << 20;
+
+fn block(c: &mut Criterion) {
+    let cipher = Rc5::new(&KEY).unwrap();
+
+    let mut group = c.benchmark_group("block");
+    group.throughput(Throughput::Bytes(8));
+    group.bench_function("encrypt", |b| {
+        b.iter(|| cipher.encrypt_block(black_box([0x5A; 8])))
+    });
+    group.bench_function("decrypt", |b| {
+        b.iter(|| cipher.decrypt_block(black_box([0x5A; 8])))
+    });
+
+    let gen = Rc5Default::new(&KEY).unwrap();
+    group.bench_function("encrypt_const_rounds", |b| {
+        b.iter(|| gen.encrypt_block(black_box([0x5A; 8])))
+    });
+    group.bench_function("decrypt_const_rounds", |b| {
+        b.iter(|| gen.decrypt_block(black_box([0x5A; 8])))
+    });
+    group.finish();
+}
+
+fn ecb(c: &mut Criterion) {
+    let cipher = Rc5::new(&KEY).unwrap();
+    let plaintext = vec![0x5A; MB];
+    let ciphertext = cipher.encrypt_ecb(&plaintext, Padding::NoPadding).unwrap();
+
+    let mut group = c.benchmark_group("ecb_1mb");
+    group.throughput(Throughput::Bytes(MB as u64));
+    group.bench_function("encrypt", |b| {
+        b.iter(|| cipher.encrypt_ecb(black_box(&plaintext), Padding::NoPadding))
+    });
+    group.bench_function("decrypt", |b| {
+        b.iter(|| cipher.decrypt_ecb(black_box(&ciphertext), Padding::NoPadding))
+    });
+    group.finish();
+}
+
+fn key_schedule(c: &mut Criterion) {
+    c.bench_function("key_schedule", |b| b.iter(|| Rc5::new(black_box(&KEY))));
+}
+
+criterion_group!(benches, block, ecb, key_schedule);
+criterion_main!(benches);
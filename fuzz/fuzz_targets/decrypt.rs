@@ -0,0 +1,52 @@
+/*
+ * Feed arbitrary keys and ciphertexts into the decryption and unpadding path
+ *
+ * Every input must either decrypt or be rejected with an `Rc5Error`, but never panic. Run with
+ *
+ *     cargo +nightly fuzz run decrypt
+ *
+ * The first input byte selects the key length, the second one the padding scheme. The IV for CBC
+ * follows the key and the remaining bytes are the ciphertext.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rc5_test::{Iv, Padding, Rc5};
+
+const PADDINGS: [Padding; 5] = [
+    Padding::Pkcs7,
+    Padding::AnsiX923,
+    Padding::Iso7816,
+    Padding::ZeroPad,
+    Padding::NoPadding,
+];
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+
+    let key_len = data[0] as usize;
+    let padding = PADDINGS[data[1] as usize % PADDINGS.len()];
+    let data = &data[2..];
+
+    if data.len() < key_len + 8 {
+        return;
+    }
+
+    let (key, data) = data.split_at(key_len);
+    let (iv, ciphertext) = data.split_at(8);
+    let mut iv_block = [0; 8];
+    iv_block.copy_from_slice(iv);
+
+    let cipher = Rc5::new(key).expect("keys of up to 255 bytes are valid");
+
+    if let Ok(plaintext) = cipher.decrypt_ecb(ciphertext, padding) {
+        assert!(plaintext.len() <= ciphertext.len());
+    }
+
+    if let Ok(plaintext) = cipher.decrypt_cbc(Iv::new(iv_block), ciphertext, padding) {
+        assert!(plaintext.len() <= ciphertext.len());
+    }
+});
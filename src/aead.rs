@@ -0,0 +1,325 @@
+/*
+ * Authenticated encryption with RC5 in CTR mode and HMAC-SHA256 (encrypt-then-MAC)
+ *
+ * Besides the nonce-based `seal`, a deterministic variant derives the nonce from the data like
+ * SIV [1], which stays secure if a nonce would otherwise be reused.
+ *
+ * [1] https://www.rfc-editor.org/rfc/rfc5297
+ */
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{ct_eq, Rc5, Rc5Error};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/** Length of the authentication tag appended to the ciphertext */
+const TAG_BYTES: usize = 32;
+
+/** Label from which the MAC key of the synthetic IV is derived */
+const SIV_LABEL: &[u8] = b"RC5-SIV";
+
+/**
+ * RC5-32/12 in CTR mode with an HMAC-SHA256 tag over nonce, associated data and ciphertext
+ *
+ * Encryption and authentication use independent keys. A nonce must never be reused with the same
+ * encryption key. As with GCM, reusing a nonce is catastrophic: the XOR of two ciphertexts under
+ * the same nonce is the XOR of their plaintexts. Use `seal_siv` if unique nonces cannot be
+ * guaranteed.
+ */
+pub struct Rc5EtmCtr {
+    cipher: Rc5,
+    mac: HmacSha256,
+    siv_mac: HmacSha256,
+}
+
+impl Rc5EtmCtr {
+    /**
+     * Create cipher for the given encryption and MAC keys
+     */
+    pub fn new(key: &[u8], mac_key: &[u8]) -> Result<Rc5EtmCtr, Rc5Error> {
+        // HMAC accepts keys of any length
+        let mac = HmacSha256::new_from_slice(mac_key).unwrap();
+
+        // Separate key for the synthetic IV, so that its MAC never doubles as a tag of `seal`
+        let mut siv_key = mac.clone();
+        siv_key.update(SIV_LABEL);
+        let siv_mac = HmacSha256::new_from_slice(&siv_key.finalize().into_bytes()).unwrap();
+
+        Ok(Rc5EtmCtr {
+            cipher: Rc5::new(key)?,
+            mac,
+            siv_mac,
+        })
+    }
+
+    /**
+     * Encrypt and authenticate the plaintext, returning the ciphertext followed by the 32-byte tag
+     *
+     * Fails with `CounterExhausted` if the plaintext is too long for the nonce, see `apply_ctr`.
+     */
+    pub fn seal(&self, nonce: [u8; 8], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        let mut out = self.cipher.apply_ctr(nonce, plaintext)?;
+        let tag = self.tag(nonce, aad, &out);
+        out.extend_from_slice(&tag.finalize().into_bytes());
+        Ok(out)
+    }
+
+    /**
+     * Verify the tag and decrypt the ciphertext
+     *
+     * The tag is compared in constant time before anything is decrypted, so no plaintext is
+     * produced for forged or corrupted input.
+     */
+    #[must_use = "dropping the result ignores authentication failures"]
+    pub fn open(&self, nonce: [u8; 8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        if sealed.len() < TAG_BYTES {
+            return Err(Rc5Error::AuthenticationFailed);
+        }
+
+        let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_BYTES);
+        let expected = self.tag(nonce, aad, ciphertext).finalize().into_bytes();
+        if !ct_eq(&expected, tag) {
+            return Err(Rc5Error::AuthenticationFailed);
+        }
+
+        self.cipher.apply_ctr(nonce, ciphertext)
+    }
+
+    /**
+     * Encrypt and authenticate the plaintext without a nonce, returning the 32-byte synthetic IV
+     * followed by the ciphertext
+     *
+     * The synthetic IV is a MAC over the associated data and plaintext, and its first eight bytes
+     * serve as the CTR nonce. Reusing the keys for any number of messages therefore only reveals
+     * whether two messages with their associated data are equal. Distinct messages get unrelated
+     * nonces, but as nonces have 64 bits, keystreams are likely to collide after about 2^32
+     * messages, or earlier for long messages. Fails with `CounterExhausted` if the plaintext is
+     * too long for its nonce.
+     */
+    pub fn seal_siv(&self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        let siv = self.siv(aad, plaintext);
+
+        let mut out = Vec::with_capacity(TAG_BYTES + plaintext.len());
+        out.extend_from_slice(&siv);
+        out.extend_from_slice(&self.cipher.apply_ctr(siv_nonce(&siv), plaintext)?);
+        Ok(out)
+    }
+
+    /**
+     * Decrypt data produced by `seal_siv` and verify its synthetic IV
+     *
+     * The synthetic IV can only be recomputed from the plaintext, so the data is decrypted first.
+     * The plaintext is discarded unless the synthetic IV matches.
+     */
+    #[must_use = "dropping the result ignores authentication failures"]
+    pub fn open_siv(&self, aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        if sealed.len() < TAG_BYTES {
+            return Err(Rc5Error::AuthenticationFailed);
+        }
+
+        let (siv, ciphertext) = sealed.split_at(TAG_BYTES);
+        // `seal_siv` never produces ciphertext exceeding the keystream of its nonce
+        let plaintext = self
+            .cipher
+            .apply_ctr(siv_nonce(siv), ciphertext)
+            .map_err(|_| Rc5Error::AuthenticationFailed)?;
+
+        if !ct_eq(&self.siv(aad, &plaintext), siv) {
+            return Err(Rc5Error::AuthenticationFailed);
+        }
+
+        Ok(plaintext)
+    }
+
+    /**
+     * Synthetic IV, i.e. MAC over the length of the associated data, associated data and plaintext
+     */
+    fn siv(&self, aad: &[u8], plaintext: &[u8]) -> [u8; TAG_BYTES] {
+        let mut mac = self.siv_mac.clone();
+        mac.update(&(aad.len() as u64).to_le_bytes());
+        mac.update(aad);
+        mac.update(plaintext);
+        mac.finalize().into_bytes().into()
+    }
+
+    /**
+     * MAC over nonce, length of the associated data, associated data and ciphertext
+     *
+     * The length prefix keeps the boundary between associated data and ciphertext unambiguous.
+     */
+    fn tag(&self, nonce: [u8; 8], aad: &[u8], ciphertext: &[u8]) -> HmacSha256 {
+        let mut mac = self.mac.clone();
+        mac.update(&nonce);
+        mac.update(&(aad.len() as u64).to_le_bytes());
+        mac.update(aad);
+        mac.update(ciphertext);
+        mac
+    }
+}
+
+/**
+ * CTR nonce taken from the synthetic IV
+ */
+fn siv_nonce(siv: &[u8]) -> [u8; 8] {
+    siv[..8].try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> Rc5EtmCtr {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5EtmCtr::new(&key, b"mac key").unwrap()
+    }
+
+    #[test]
+    fn seal_open_round_trip() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+
+        for len in [0, 1, 8, 21].iter() {
+            let plaintext: Vec<u8> = (0..*len as u8).collect();
+            let sealed = cipher.seal(nonce, b"header", &plaintext).unwrap();
+
+            assert_eq!(sealed.len(), len + TAG_BYTES);
+            assert_eq!(
+                sealed[..*len],
+                cipher.cipher.apply_ctr(nonce, &plaintext).unwrap()[..]
+            );
+            assert_eq!(cipher.open(nonce, b"header", &sealed).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn open_rejects_tampering() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+        let sealed = cipher.seal(nonce, b"header", b"attack at dawn").unwrap();
+
+        for i in 0..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(
+                cipher.open(nonce, b"header", &tampered),
+                Err(Rc5Error::AuthenticationFailed)
+            );
+        }
+
+        assert_eq!(
+            cipher.open([0; 8], b"header", &sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            cipher.open(nonce, b"footer", &sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            cipher.open(nonce, b"header", &sealed[..TAG_BYTES - 1]),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn open_rejects_modified_aad() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+        let aad = b"protocol header v1";
+        let sealed = cipher.seal(nonce, aad, b"attack at dawn").unwrap();
+
+        for i in 0..aad.len() {
+            let mut modified = *aad;
+            modified[i] ^= 1;
+            assert_eq!(
+                cipher.open(nonce, &modified, &sealed),
+                Err(Rc5Error::AuthenticationFailed),
+                "byte {}",
+                i
+            );
+        }
+
+        assert_eq!(
+            cipher.open(nonce, &aad[..aad.len() - 1], &sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            cipher.open(nonce, b"", &sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+        assert_eq!(cipher.open(nonce, aad, &sealed).unwrap(), b"attack at dawn");
+    }
+
+    #[test]
+    fn seal_siv_round_trip() {
+        let cipher = cipher();
+
+        for len in [0, 1, 8, 21].iter() {
+            let plaintext: Vec<u8> = (0..*len as u8).collect();
+            let sealed = cipher.seal_siv(b"header", &plaintext).unwrap();
+
+            assert_eq!(sealed.len(), TAG_BYTES + len);
+            assert_eq!(
+                sealed[TAG_BYTES..],
+                cipher
+                    .cipher
+                    .apply_ctr(siv_nonce(&sealed), &plaintext)
+                    .unwrap()[..]
+            );
+            assert_eq!(cipher.open_siv(b"header", &sealed).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn seal_siv_deterministic() {
+        let cipher = cipher();
+        let sealed = cipher.seal_siv(b"header", b"attack at dawn").unwrap();
+
+        assert_eq!(
+            cipher.seal_siv(b"header", b"attack at dawn").unwrap(),
+            sealed
+        );
+        assert_ne!(
+            cipher.seal_siv(b"header", b"attack at dusk").unwrap()[..8],
+            sealed[..8]
+        );
+        assert_ne!(
+            cipher.seal_siv(b"footer", b"attack at dawn").unwrap()[..8],
+            sealed[..8]
+        );
+    }
+
+    #[test]
+    fn open_siv_rejects_tampering() {
+        let cipher = cipher();
+        let sealed = cipher.seal_siv(b"header", b"attack at dawn").unwrap();
+
+        for i in 0..sealed.len() {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(
+                cipher.open_siv(b"header", &tampered),
+                Err(Rc5Error::AuthenticationFailed)
+            );
+        }
+
+        assert_eq!(
+            cipher.open_siv(b"footer", &sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+        assert_eq!(
+            cipher.open_siv(b"header", &sealed[..TAG_BYTES - 1]),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+
+        // A nonce-based tag does not authenticate SIV data and vice versa
+        let nonce_sealed = cipher.seal([0; 8], b"header", b"").unwrap();
+        assert_eq!(
+            cipher.open_siv(b"header", &nonce_sealed),
+            Err(Rc5Error::AuthenticationFailed)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * Base64 encoding of ECB ciphertext, e.g. for JSON or other text fields
+ */
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::{Padding, Rc5, Rc5Error};
+
+impl Rc5 {
+    /**
+     * Encrypt data like `encrypt_ecb`, returning the ciphertext in standard padded Base64
+     */
+    pub fn encrypt_ecb_base64(&self, data: &[u8], padding: Padding) -> Result<String, Rc5Error> {
+        Ok(STANDARD.encode(self.encrypt_ecb(data, padding)?))
+    }
+
+    /**
+     * Decode Base64 ciphertext and decrypt it like `decrypt_ecb`
+     *
+     * Fails with `Encoding` if the input is not valid standard Base64.
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_ecb_base64(&self, data: &str, padding: Padding) -> Result<Vec<u8>, Rc5Error> {
+        let ciphertext = STANDARD.decode(data).map_err(|_| Rc5Error::Encoding)?;
+        self.decrypt_ecb(&ciphertext, padding)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn encrypt_ecb_base64() {
+        let cipher = cipher();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        // 2ddc149bcf088b9e
+        assert_eq!(
+            cipher
+                .encrypt_ecb_base64(&plaintext, Padding::NoPadding)
+                .unwrap(),
+            "LdwUm88Ii54="
+        );
+        assert_eq!(
+            cipher
+                .decrypt_ecb_base64("LdwUm88Ii54=", Padding::NoPadding)
+                .unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        let cipher = cipher();
+
+        for len in 0..=24 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let encoded = cipher
+                .encrypt_ecb_base64(&plaintext, Padding::Pkcs7)
+                .unwrap();
+            assert_eq!(
+                cipher.decrypt_ecb_base64(&encoded, Padding::Pkcs7).unwrap(),
+                plaintext
+            );
+        }
+    }
+
+    #[test]
+    fn decrypt_ecb_base64_invalid() {
+        let cipher = cipher();
+
+        for invalid in ["LdwUm88Ii54", "LdwUm88Ii54=!", "Ldw Um88Ii54="].iter() {
+            assert_eq!(
+                cipher.decrypt_ecb_base64(invalid, Padding::Pkcs7),
+                Err(Rc5Error::Encoding)
+            );
+        }
+
+        // Valid Base64, but not whole blocks
+        assert_eq!(
+            cipher.decrypt_ecb_base64("AAAA", Padding::Pkcs7),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+}
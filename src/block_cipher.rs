@@ -0,0 +1,176 @@
+/*
+ * Integration with the RustCrypto `cipher` traits
+ *
+ * This allows using RC5 with generic mode implementations such as the `cbc`, `ctr` and `cfb-mode`
+ * crates.
+ */
+
+use cipher::consts::{U16, U8};
+use cipher::{
+    impl_simple_block_encdec, AlgorithmName, BlockCipher, InvalidLength, Key, KeyInit, KeySizeUser,
+};
+use core::fmt;
+
+use crate::Rc5;
+
+/**
+ * RC5-32/12 wrapped for use with the `cipher` traits
+ *
+ * `KeyInit::new` takes the default 16-byte key, `KeyInit::new_from_slice` accepts any key length
+ * supported by `Rc5`.
+ */
+pub struct Rc5BlockCipher {
+    cipher: Rc5,
+}
+
+impl Rc5BlockCipher {
+    /**
+     * Wrap an already configured cipher
+     */
+    pub fn from_cipher(cipher: Rc5) -> Rc5BlockCipher {
+        Rc5BlockCipher { cipher }
+    }
+}
+
+impl KeySizeUser for Rc5BlockCipher {
+    type KeySize = U16;
+}
+
+impl KeyInit for Rc5BlockCipher {
+    fn new(key: &Key<Self>) -> Self {
+        Rc5BlockCipher::new_from_slice(key).unwrap()
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        let cipher = Rc5::new(key).map_err(|_| InvalidLength)?;
+        Ok(Rc5BlockCipher::from_cipher(cipher))
+    }
+}
+
+impl BlockCipher for Rc5BlockCipher {}
+
+impl AlgorithmName for Rc5BlockCipher {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RC5-32")
+    }
+}
+
+impl_simple_block_encdec!(
+    Rc5BlockCipher, U8, state, block,
+    encrypt: {
+        let output = state.cipher.encrypt_block((*block.get_in()).into());
+        block.get_out().copy_from_slice(&output);
+    }
+    decrypt: {
+        let output = state.cipher.decrypt_block((*block.get_in()).into());
+        block.get_out().copy_from_slice(&output);
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cipher::{Block, BlockDecrypt, BlockEncrypt};
+
+    #[test]
+    fn block_encrypt_a() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
+
+        let cipher = Rc5BlockCipher::new(Key::<Rc5BlockCipher>::from_slice(&key));
+
+        let mut block = Block::<Rc5BlockCipher>::clone_from_slice(&plaintext);
+        cipher.encrypt_block(&mut block);
+        assert_eq!(block.as_slice(), ciphertext);
+
+        cipher.decrypt_block(&mut block);
+        assert_eq!(block.as_slice(), plaintext);
+    }
+
+    #[test]
+    fn cbc_crate_interop() {
+        use cipher::block_padding::Pkcs7;
+        use cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+
+        use crate::Iv;
+
+        let key: Vec<u8> = (0..16).collect();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let encryptor =
+            cbc::Encryptor::<Rc5BlockCipher>::new_from_slices(&key, iv.as_ref()).unwrap();
+        let ciphertext = encryptor.encrypt_padded_vec_mut::<Pkcs7>(&plaintext);
+        assert_eq!(
+            ciphertext,
+            Rc5::new(&key)
+                .unwrap()
+                .encrypt_cbc(iv, &plaintext, crate::Padding::Pkcs7)
+                .unwrap()
+        );
+
+        let decryptor =
+            cbc::Decryptor::<Rc5BlockCipher>::new_from_slices(&key, iv.as_ref()).unwrap();
+        let result = decryptor
+            .decrypt_padded_vec_mut::<Pkcs7>(&ciphertext)
+            .unwrap();
+        assert_eq!(result, plaintext);
+    }
+
+    #[test]
+    fn cbc_crate_cts_variants() {
+        use cipher::block_padding::NoPadding;
+        use cipher::{BlockEncryptMut, KeyIvInit};
+
+        use crate::{CtsVariant, Iv};
+
+        let key: Vec<u8> = (0..16).collect();
+        let iv = Iv::new([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+        let cipher = Rc5::new(&key).unwrap();
+
+        for len in 9..=32usize {
+            let plaintext: Vec<u8> = (0..len as u8).map(|i| 0x30 + i).collect();
+
+            // CBC blocks over the zero-padded data, computed by the `cbc` crate
+            let mut padded = plaintext.clone();
+            padded.resize(len.div_ceil(8) * 8, 0);
+            let cbc = cbc::Encryptor::<Rc5BlockCipher>::new_from_slices(&key, iv.as_ref())
+                .unwrap()
+                .encrypt_padded_vec_mut::<NoPadding>(&padded);
+
+            // C_1 ... C_n-2, then C_n-1 truncated to d bytes and C_n as defined by SP 800-38A
+            let n = cbc.len() / 8;
+            let d = len - 8 * (n - 1);
+            let head = &cbc[..8 * (n - 2)];
+            let stolen = &cbc[8 * (n - 2)..][..d];
+            let last = &cbc[8 * (n - 1)..];
+            let cs1 = [head, stolen, last].concat();
+            let cs3 = [head, last, stolen].concat();
+            let cs2 = if d == 8 { cs1.clone() } else { cs3.clone() };
+
+            for (variant, expected) in [
+                (CtsVariant::Cs1, cs1),
+                (CtsVariant::Cs2, cs2),
+                (CtsVariant::Cs3, cs3),
+            ]
+            .iter()
+            {
+                assert_eq!(
+                    &cipher.encrypt_cbc_cts(iv, &plaintext, *variant).unwrap(),
+                    expected,
+                    "{:?}, length {}",
+                    variant,
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn new_from_slice_variable_key() {
+        let key: Vec<u8> = (0..10).collect();
+        assert!(Rc5BlockCipher::new_from_slice(&key).is_ok());
+        assert!(Rc5BlockCipher::new_from_slice(&[0; 256]).is_err());
+    }
+}
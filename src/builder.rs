@@ -0,0 +1,322 @@
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use crate::{ByteOrder, Rc5Cipher, Rc5Error, Word, DEFAULT_ROUNDS, MAX_KEY_BYTES, MAX_ROUNDS};
+
+/**
+ * Builder combining the three RC5 parameters, as in the variant name RC5-w/r/b
+ *
+ * The word size w is chosen with `word`, which defaults to 32 bits, the number of rounds r with
+ * `rounds`, which defaults to `DEFAULT_ROUNDS`, and the key, whose length is b, with `key`.
+ * A key is mandatory. Bytes are packed into words little-endian unless `byte_order` says
+ * otherwise.
+ *
+ * A builder can also be parsed from the variant name, see the `FromStr` implementation.
+ */
+pub struct Rc5Builder<'a, W: Word = u32> {
+    rounds: usize,
+    key: Option<&'a [u8]>,
+    key_len: Option<usize>,
+    byte_order: ByteOrder,
+    word: PhantomData<W>,
+}
+
+impl<'a> Rc5Builder<'a, u32> {
+    /**
+     * Create builder for RC5-32 with `DEFAULT_ROUNDS`
+     */
+    pub fn new() -> Rc5Builder<'a, u32> {
+        Rc5Builder {
+            rounds: DEFAULT_ROUNDS,
+            key: None,
+            key_len: None,
+            byte_order: ByteOrder::default(),
+            word: PhantomData,
+        }
+    }
+}
+
+impl<'a> Default for Rc5Builder<'a, u32> {
+    fn default() -> Rc5Builder<'a, u32> {
+        Rc5Builder::new()
+    }
+}
+
+impl<'a, W: Word> Rc5Builder<'a, W> {
+    /**
+     * Select the word size
+     */
+    pub fn word<V: Word>(self) -> Rc5Builder<'a, V> {
+        Rc5Builder {
+            rounds: self.rounds,
+            key: self.key,
+            key_len: self.key_len,
+            byte_order: self.byte_order,
+            word: PhantomData,
+        }
+    }
+
+    /**
+     * Select the number of rounds
+     */
+    pub fn rounds(mut self, rounds: usize) -> Rc5Builder<'a, W> {
+        self.rounds = rounds;
+        self
+    }
+
+    /**
+     * Select the secret key
+     */
+    pub fn key(mut self, key: &'a [u8]) -> Rc5Builder<'a, W> {
+        self.key = Some(key);
+        self
+    }
+
+    /**
+     * Select the packing of bytes into words
+     */
+    pub fn byte_order(mut self, byte_order: ByteOrder) -> Rc5Builder<'a, W> {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /**
+     * Validate the parameters and create the cipher
+     *
+     * If the builder was parsed from a variant name, the key must have the length given there,
+     * otherwise building fails with `KeyLengthMismatch`.
+     */
+    pub fn build(self) -> Result<Rc5Cipher<W>, Rc5Error> {
+        let key = self.key.ok_or(Rc5Error::MissingKey)?;
+
+        if let Some(expected) = self.key_len {
+            if key.len() != expected {
+                return Err(Rc5Error::KeyLengthMismatch {
+                    got: key.len(),
+                    expected,
+                });
+            }
+        }
+
+        Rc5Cipher::with_byte_order(key, self.rounds, self.byte_order)
+    }
+}
+
+/**
+ * Parse a variant name such as "RC5-32/12/16" into a builder, which only lacks the key
+ *
+ * The word size is part of the builder's type and cannot be chosen by the parsed name. It must
+ * match `W`, which defaults to `u32`, otherwise parsing fails with `InvalidWordSize`. Other word
+ * sizes are parsed by naming the type, e.g. `parse::<Rc5Builder<u64>>()`. The number of
+ * rounds and the key length are checked against `MAX_ROUNDS` and `MAX_KEY_BYTES`. Any other
+ * malformed name fails with `InvalidSpec`.
+ */
+impl<'a, W: Word> FromStr for Rc5Builder<'a, W> {
+    type Err = Rc5Error;
+
+    fn from_str(s: &str) -> Result<Rc5Builder<'a, W>, Rc5Error> {
+        let params = s
+            .get(..4)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("RC5-"))
+            .map(|_| &s[4..])
+            .ok_or(Rc5Error::InvalidSpec)?;
+
+        let mut fields = params.split('/').map(parse_field);
+        let (word_bits, rounds, key_len) = match (fields.next(), fields.next(), fields.next()) {
+            (Some(w), Some(r), Some(b)) if fields.next().is_none() => (w?, r?, b?),
+            _ => return Err(Rc5Error::InvalidSpec),
+        };
+
+        if word_bits != W::BITS {
+            return Err(Rc5Error::InvalidWordSize {
+                got: word_bits,
+                expected: W::BITS,
+            });
+        }
+
+        if rounds > MAX_ROUNDS {
+            return Err(Rc5Error::InvalidRounds {
+                got: rounds,
+                expected: MAX_ROUNDS,
+            });
+        }
+
+        if key_len > MAX_KEY_BYTES {
+            return Err(Rc5Error::InvalidKeyLength {
+                got: key_len,
+                expected: MAX_KEY_BYTES,
+            });
+        }
+
+        let mut builder = Rc5Builder::new().word::<W>().rounds(rounds);
+        builder.key_len = Some(key_len);
+        Ok(builder)
+    }
+}
+
+/**
+ * Parse a decimal field of a variant name, rejecting signs and empty fields
+ */
+fn parse_field(field: &str) -> Result<usize, Rc5Error> {
+    if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Rc5Error::InvalidSpec);
+    }
+
+    field.parse().map_err(|_| Rc5Error::InvalidSpec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Rc5;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn build_defaults() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5Builder::new().key(&key).build().unwrap();
+        let expected = Rc5::new(&key).unwrap();
+
+        assert_eq!(cipher.rounds(), DEFAULT_ROUNDS);
+        assert_eq!(cipher.encrypt_block([0; 8]), expected.encrypt_block([0; 8]));
+    }
+
+    #[test]
+    fn build_variant() {
+        let key: Vec<u8> = (0..24).collect();
+        let cipher = Rc5Builder::new()
+            .word::<u64>()
+            .rounds(24)
+            .key(&key)
+            .build()
+            .unwrap();
+        let expected = Rc5Cipher::<u64>::with_rounds(&key, 24).unwrap();
+
+        assert_eq!(cipher.rounds(), 24);
+        assert_eq!(
+            cipher.encrypt_block([0; 16]),
+            expected.encrypt_block([0; 16])
+        );
+    }
+
+    #[test]
+    fn build_byte_order() {
+        let cipher = Rc5Builder::new()
+            .byte_order(ByteOrder::BigEndian)
+            .key(&[0; 16])
+            .build()
+            .unwrap();
+        assert_eq!(cipher.byte_order(), ByteOrder::BigEndian);
+    }
+
+    #[test]
+    fn build_invalid() {
+        assert_eq!(Rc5Builder::new().build().err(), Some(Rc5Error::MissingKey));
+        assert_eq!(
+            Rc5Builder::new().rounds(256).key(&[0; 16]).build().err(),
+            Some(Rc5Error::InvalidRounds {
+                got: 256,
+                expected: MAX_ROUNDS
+            })
+        );
+        assert_eq!(
+            Rc5Builder::new().key(&[0; 256]).build().err(),
+            Some(Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: MAX_KEY_BYTES
+            })
+        );
+    }
+
+    #[test]
+    fn parse_spec() {
+        let key: Vec<u8> = (0..16).collect();
+        let builder: Rc5Builder<u32> = "RC5-32/12/16".parse().unwrap();
+        let cipher = builder.key(&key).build().unwrap();
+        assert_eq!(
+            cipher.encrypt_block([0; 8]),
+            Rc5::new(&key).unwrap().encrypt_block([0; 8])
+        );
+
+        let cipher = "RC5-32/12/16"
+            .parse::<Rc5Builder>()
+            .unwrap()
+            .key(&key)
+            .build()
+            .unwrap();
+        assert_eq!(cipher.key_len(), Some(16));
+
+        let key: Vec<u8> = (0..24).collect();
+        let cipher = "rc5-64/24/24"
+            .parse::<Rc5Builder<u64>>()
+            .unwrap()
+            .key(&key)
+            .build()
+            .unwrap();
+        assert_eq!(cipher.rounds(), 24);
+        assert_eq!(cipher.key_len(), Some(24));
+    }
+
+    #[test]
+    fn parse_spec_key_length_mismatch() {
+        let builder: Rc5Builder<u32> = "RC5-32/12/16".parse().unwrap();
+        let err = builder.key(&[1; 10]).build().err().unwrap();
+        assert_eq!(
+            err,
+            Rc5Error::KeyLengthMismatch {
+                got: 10,
+                expected: 16
+            }
+        );
+        assert_eq!(err.to_string(), "key has 10 bytes, expected 16");
+    }
+
+    #[test]
+    fn parse_spec_invalid() {
+        let malformed = [
+            "",
+            "RC5",
+            "RC5-",
+            "RC6-32/12/16",
+            "RC5-32/12",
+            "RC5-32/12/16/1",
+            "RC5-32//16",
+            "RC5-32/+12/16",
+            "RC5-32/12/16 ",
+            "RC5-32/x/16",
+            "RC5-32/99999999999999999999999/16",
+        ];
+        for spec in malformed.iter() {
+            assert_eq!(
+                spec.parse::<Rc5Builder<u32>>().err(),
+                Some(Rc5Error::InvalidSpec),
+                "{:?}",
+                spec
+            );
+        }
+
+        assert_eq!(
+            "RC5-64/12/16".parse::<Rc5Builder<u32>>().err(),
+            Some(Rc5Error::InvalidWordSize {
+                got: 64,
+                expected: 32
+            })
+        );
+        assert_eq!(
+            "RC5-32/256/16".parse::<Rc5Builder<u32>>().err(),
+            Some(Rc5Error::InvalidRounds {
+                got: 256,
+                expected: MAX_ROUNDS
+            })
+        );
+        assert_eq!(
+            "RC5-32/12/256".parse::<Rc5Builder<u32>>().err(),
+            Some(Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: MAX_KEY_BYTES
+            })
+        );
+    }
+}
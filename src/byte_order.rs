@@ -0,0 +1,39 @@
+use crate::Word;
+
+/**
+ * Order in which bytes of the key and of blocks are packed into words
+ *
+ * The reference implementation and the published test vectors use little-endian packing, which
+ * is the default. Some deployments pack bytes big-endian instead.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ByteOrder {
+    /** First byte is the least significant byte of the word */
+    #[default]
+    LittleEndian,
+    /** First byte is the most significant byte of the word */
+    BigEndian,
+}
+
+impl ByteOrder {
+    /**
+     * Read word from exactly `W::BYTES` bytes
+     */
+    pub(crate) fn read_word<W: Word>(self, bytes: &[u8]) -> W {
+        match self {
+            ByteOrder::LittleEndian => W::from_le_bytes(bytes),
+            ByteOrder::BigEndian => W::from_be_bytes(bytes),
+        }
+    }
+
+    /**
+     * Write word to exactly `W::BYTES` bytes
+     */
+    pub(crate) fn write_word<W: Word>(self, word: W, bytes: &mut [u8]) {
+        match self {
+            ByteOrder::LittleEndian => word.write_le_bytes(bytes),
+            ByteOrder::BigEndian => word.write_be_bytes(bytes),
+        }
+    }
+}
@@ -0,0 +1,653 @@
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use crate::padding::{pad_in_place, unpad};
+use crate::{xor_in_place, Iv, Padding, Rc5, Rc5Error, BLOCK_BYTES};
+
+/**
+ * Ordering of the last two blocks for ciphertext stealing, as numbered in the addendum to NIST
+ * SP 800-38A
+ *
+ * All variants truncate the penultimate ciphertext block to the length of the final partial
+ * plaintext block. They only differ in where it is placed.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtsVariant {
+    /** Truncated penultimate block followed by the last one, i.e. the order of plain CBC */
+    Cs1,
+    /** CS1 for block-aligned data, CS3 otherwise */
+    Cs2,
+    /** Last block followed by the truncated penultimate one, even if aligned (e.g. Kerberos) */
+    Cs3,
+}
+
+/**
+ * Cipher block chaining (CBC) mode
+ *
+ * Every plaintext block is XORed with the previous ciphertext block before encryption, the first
+ * one with the IV. The IV is managed by the caller and not part of the output.
+ */
+impl Rc5 {
+    /**
+     * Encrypt data of arbitrary length, applying `padding`
+     *
+     * Fails with `InvalidBlockLength` if `padding` is `NoPadding` and the data does not consist of
+     * whole blocks.
+     */
+    pub fn encrypt_cbc(&self, iv: Iv, data: &[u8], padding: Padding) -> Result<Vec<u8>, Rc5Error> {
+        let mut out = Vec::with_capacity(data.len() + BLOCK_BYTES);
+        out.extend_from_slice(data);
+        self.encrypt_cbc_in_place(iv, &mut out, padding)?;
+        Ok(out)
+    }
+
+    /**
+     * Decrypt data and strip its `padding`
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_cbc(&self, iv: Iv, data: &[u8], padding: Padding) -> Result<Vec<u8>, Rc5Error> {
+        let mut out = data.to_vec();
+        self.decrypt_cbc_in_place(iv, &mut out, padding)?;
+        Ok(out)
+    }
+
+    /**
+     * Encrypt the buffer in place like `encrypt_cbc`
+     *
+     * A `Vec` is required since padding grows the buffer, which reuses its allocation if the
+     * capacity suffices.
+     */
+    pub fn encrypt_cbc_in_place(
+        &self,
+        iv: Iv,
+        buf: &mut Vec<u8>,
+        padding: Padding,
+    ) -> Result<(), Rc5Error> {
+        pad_in_place(buf, padding)?;
+        let mut prev = iv.into_bytes();
+
+        for chunk in buf.chunks_exact_mut(BLOCK_BYTES) {
+            self.encrypt_cbc_block(&mut prev, chunk.try_into().unwrap());
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Decrypt the buffer in place like `decrypt_cbc`, truncating it to strip the padding
+     *
+     * The buffer is left untouched if it does not consist of whole blocks. If the padding is
+     * invalid, it holds the decrypted data including the malformed padding.
+     */
+    #[must_use = "the buffer does not hold the plaintext if decryption failed"]
+    pub fn decrypt_cbc_in_place(
+        &self,
+        iv: Iv,
+        buf: &mut Vec<u8>,
+        padding: Padding,
+    ) -> Result<(), Rc5Error> {
+        if !buf.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let mut prev = iv.into_bytes();
+
+        for chunk in buf.chunks_exact_mut(BLOCK_BYTES) {
+            self.decrypt_cbc_block(&mut prev, chunk.try_into().unwrap());
+        }
+
+        let len = unpad(buf, padding)?.len();
+        buf.truncate(len);
+        Ok(())
+    }
+
+    /**
+     * Encrypt block-aligned data chained to `prev`, returning the ciphertext and its last block
+     *
+     * This encrypts a message in chunks without concatenating them: `prev` is the IV for the
+     * first call and the returned block for every following one. The last chunk is passed to
+     * `encrypt_cbc_finish`, which applies the padding. Fails with `InvalidBlockLength` if the data
+     * does not consist of whole blocks. Empty data returns `prev` unchanged.
+     */
+    pub fn encrypt_cbc_update(&self, prev: Iv, data: &[u8]) -> Result<(Vec<u8>, Iv), Rc5Error> {
+        if !data.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let mut out = data.to_vec();
+        let mut prev = prev.into_bytes();
+        for chunk in out.chunks_exact_mut(BLOCK_BYTES) {
+            self.encrypt_cbc_block(&mut prev, chunk.try_into().unwrap());
+        }
+
+        Ok((out, Iv::new(prev)))
+    }
+
+    /**
+     * Encrypt the final chunk of data of arbitrary length chained to `prev`, see
+     * `encrypt_cbc_update`
+     *
+     * This is `encrypt_cbc` with `prev` as the IV.
+     */
+    pub fn encrypt_cbc_finish(
+        &self,
+        prev: Iv,
+        data: &[u8],
+        padding: Padding,
+    ) -> Result<Vec<u8>, Rc5Error> {
+        self.encrypt_cbc(prev, data, padding)
+    }
+
+    /**
+     * Encrypt data of at least one block without padding, using ciphertext stealing
+     *
+     * The final partial block is zero-padded and chained as usual, then the penultimate ciphertext
+     * block is truncated to the length of the final plaintext block and placed as given by
+     * `variant`. With `CtsVariant::Cs3`, block-aligned data therefore has its last two blocks
+     * swapped compared to plain CBC. Data of a single block is encrypted as in plain CBC. Fails
+     * with `InvalidBlockLength` for data shorter than a block.
+     */
+    pub fn encrypt_cbc_cts(
+        &self,
+        iv: Iv,
+        data: &[u8],
+        variant: CtsVariant,
+    ) -> Result<Vec<u8>, Rc5Error> {
+        let (_, out) = self.encrypt_cbc_cts_trace(iv, data, variant)?;
+        Ok(out)
+    }
+
+    /**
+     * Encrypt data like `encrypt_cbc_cts`, also returning the CBC blocks before the stealing step
+     *
+     * The blocks are plain CBC over the data with its final partial block zero-padded, which all
+     * variants have in common. The output is derived from them by truncating and reordering the
+     * last two blocks. Comparing the blocks against another implementation tells apart a mismatch
+     * in the chaining from one in the ordering of the final blocks.
+     */
+    pub fn encrypt_cbc_cts_trace(
+        &self,
+        iv: Iv,
+        data: &[u8],
+        variant: CtsVariant,
+    ) -> Result<(Vec<[u8; 8]>, Vec<u8>), Rc5Error> {
+        if data.len() < BLOCK_BYTES {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let mut prev = iv.into_bytes();
+        let blocks: Vec<[u8; 8]> = data
+            .chunks(BLOCK_BYTES)
+            .map(|chunk| {
+                let mut block = [0; 8];
+                block[..chunk.len()].copy_from_slice(chunk);
+                self.encrypt_cbc_block(&mut prev, &mut block);
+                block
+            })
+            .collect();
+
+        let mut out = Vec::with_capacity(data.len());
+        match &blocks[..] {
+            [head @ .., penultimate, last] => {
+                let (_, tail_len) = cts_split(data.len());
+                let stolen = &penultimate[..tail_len];
+
+                out.extend(head.iter().flatten());
+                if cts_swapped(variant, tail_len) {
+                    out.extend_from_slice(stolen);
+                    out.extend_from_slice(last);
+                } else {
+                    out.extend_from_slice(last);
+                    out.extend_from_slice(stolen);
+                }
+            }
+            // A single block is encrypted as in plain CBC
+            single => out.extend(single.iter().flatten()),
+        }
+
+        Ok((blocks, out))
+    }
+
+    /**
+     * Decrypt data produced by `encrypt_cbc_cts` with the same `variant`
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_cbc_cts(
+        &self,
+        iv: Iv,
+        data: &[u8],
+        variant: CtsVariant,
+    ) -> Result<Vec<u8>, Rc5Error> {
+        if data.len() < BLOCK_BYTES {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let mut out = data.to_vec();
+        let mut prev = iv.into_bytes();
+
+        if out.len() == BLOCK_BYTES {
+            self.decrypt_cbc_block(&mut prev, (&mut out[..]).try_into().unwrap());
+            return Ok(out);
+        }
+
+        // Restore the CS3 order, which the rest of the decryption expects
+        let (head, tail_len) = cts_split(out.len());
+        if cts_swapped(variant, tail_len) {
+            out[head..].rotate_right(BLOCK_BYTES);
+        }
+
+        for chunk in out[..head].chunks_exact_mut(BLOCK_BYTES) {
+            self.decrypt_cbc_block(&mut prev, chunk.try_into().unwrap());
+        }
+
+        // The last block was chained with the penultimate ciphertext block, whose truncated
+        // bytes are recovered from the zero padding
+        let last = self.decrypt_block(out[head..head + BLOCK_BYTES].try_into().unwrap());
+        let mut penultimate = last;
+        penultimate[..tail_len].copy_from_slice(&out[head + BLOCK_BYTES..]);
+
+        let mut tail = last;
+        xor_in_place(&mut tail, &penultimate);
+
+        self.decrypt_cbc_block(&mut prev, &mut penultimate);
+        out[head..head + BLOCK_BYTES].copy_from_slice(&penultimate);
+        out[head + BLOCK_BYTES..].copy_from_slice(&tail[..tail_len]);
+        Ok(out)
+    }
+
+    /**
+     * CBC-MAC over block-aligned data, i.e. the last ciphertext block of CBC with a zero IV
+     *
+     * Raw CBC-MAC is only secure for messages of one fixed length. If messages of different
+     * lengths are authenticated with the same key, tags for new messages can be forged from known
+     * ones, e.g. by appending a block XORed with a known tag. Prefer `Rc5EtmCtr` unless
+     * interoperability requires CBC-MAC.
+     */
+    #[must_use = "the tag must be compared to authenticate the data"]
+    pub fn cbc_mac(&self, data: &[u8]) -> Result<[u8; 8], Rc5Error> {
+        if data.is_empty() {
+            return Err(Rc5Error::EmptyInput);
+        }
+        if !data.len().is_multiple_of(BLOCK_BYTES) {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let mut prev = [0; 8];
+        for chunk in data.chunks_exact(BLOCK_BYTES) {
+            let mut block: [u8; 8] = chunk.try_into().unwrap();
+            self.encrypt_cbc_block(&mut prev, &mut block);
+        }
+
+        Ok(prev)
+    }
+
+    /**
+     * Encrypt a single block in place, chaining it with the previous ciphertext block
+     */
+    pub(crate) fn encrypt_cbc_block(&self, prev: &mut [u8; 8], block: &mut [u8; 8]) {
+        xor_in_place(block, prev);
+        self.encrypt_block_in_place(block);
+        *prev = *block;
+    }
+
+    /**
+     * Decrypt a single block in place, chaining it with the previous ciphertext block
+     */
+    pub(crate) fn decrypt_cbc_block(&self, prev: &mut [u8; 8], block: &mut [u8; 8]) {
+        let ciphertext = *block;
+
+        self.decrypt_block_in_place(block);
+        xor_in_place(block, prev);
+
+        *prev = ciphertext;
+    }
+}
+
+/**
+ * Split data longer than a block for ciphertext stealing
+ *
+ * Returns the length of the leading whole blocks, which are processed as in plain CBC, and the
+ * length of the final block, which is between 1 and 8 bytes. The block in between is the
+ * penultimate one.
+ */
+fn cts_split(len: usize) -> (usize, usize) {
+    let tail_len = match len % BLOCK_BYTES {
+        0 => BLOCK_BYTES,
+        rem => rem,
+    };
+
+    (len - BLOCK_BYTES - tail_len, tail_len)
+}
+
+/**
+ * Whether the final blocks of `variant` are in the opposite order of CS3, in which they are
+ * produced
+ */
+fn cts_swapped(variant: CtsVariant, tail_len: usize) -> bool {
+    match variant {
+        CtsVariant::Cs1 => true,
+        CtsVariant::Cs2 => tail_len == BLOCK_BYTES,
+        CtsVariant::Cs3 => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn encrypt_cbc_update_chunks() {
+        let cipher = cipher();
+        let iv = Iv::new([0x5A; 8]);
+        let plaintext: Vec<u8> = (0..45).collect();
+        let expected = cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap();
+
+        let mut out = Vec::new();
+        let mut prev = iv;
+        for chunk in [&plaintext[..8], &plaintext[8..8], &plaintext[8..32]].iter() {
+            let (ciphertext, last) = cipher.encrypt_cbc_update(prev, chunk).unwrap();
+            out.extend_from_slice(&ciphertext);
+            prev = last;
+        }
+        assert_eq!(prev.into_bytes(), out[24..32]);
+
+        out.extend(
+            cipher
+                .encrypt_cbc_finish(prev, &plaintext[32..], Padding::Pkcs7)
+                .unwrap(),
+        );
+        assert_eq!(out, expected);
+
+        assert_eq!(
+            cipher.encrypt_cbc_update(iv, &plaintext[..9]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn encrypt_cbc_a() {
+        let iv = Iv::new([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+        let plaintext: Vec<u8> = (0..16).collect();
+
+        let cipher = cipher();
+        let result = cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap();
+        assert_eq!(result.len(), 24);
+
+        let mut prev = iv.into_bytes();
+        for (i, chunk) in result.chunks(8).enumerate() {
+            let mut block = if i < 2 {
+                plaintext[i * 8..][..8].try_into().unwrap()
+            } else {
+                [8; 8]
+            };
+            xor_in_place(&mut block, &prev);
+
+            prev = cipher.encrypt_block(block);
+            assert_eq!(chunk, prev);
+        }
+    }
+
+    #[test]
+    fn cbc_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for len in 0..=24 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let ciphertext = cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap();
+
+            assert_eq!(ciphertext.len(), (len / 8 + 1) * 8);
+            assert_eq!(
+                cipher.decrypt_cbc(iv, &ciphertext, Padding::Pkcs7).unwrap(),
+                plaintext
+            );
+        }
+    }
+
+    #[test]
+    fn cbc_in_place() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let mut buf = plaintext.clone();
+        cipher
+            .encrypt_cbc_in_place(iv, &mut buf, Padding::Pkcs7)
+            .unwrap();
+        assert_eq!(
+            buf,
+            cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap()
+        );
+
+        cipher
+            .decrypt_cbc_in_place(iv, &mut buf, Padding::Pkcs7)
+            .unwrap();
+        assert_eq!(buf, plaintext);
+    }
+
+    #[test]
+    fn cbc_cts_13_bytes() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..13).collect();
+
+        let ciphertext = cipher
+            .encrypt_cbc_cts(iv, &plaintext, CtsVariant::Cs3)
+            .unwrap();
+        assert_eq!(ciphertext.len(), 13);
+
+        // Same as CBC with zero padding, with the last two blocks swapped and truncated
+        let cbc = cipher
+            .encrypt_cbc(iv, &plaintext, Padding::ZeroPad)
+            .unwrap();
+        assert_eq!(ciphertext[..8], cbc[8..]);
+        assert_eq!(ciphertext[8..], cbc[..5]);
+
+        assert_eq!(
+            cipher
+                .decrypt_cbc_cts(iv, &ciphertext, CtsVariant::Cs3)
+                .unwrap(),
+            plaintext
+        );
+    }
+
+    #[test]
+    fn cbc_cts_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for len in 8..=40 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let ciphertext = cipher
+                .encrypt_cbc_cts(iv, &plaintext, CtsVariant::Cs3)
+                .unwrap();
+
+            assert_eq!(ciphertext.len(), len);
+            assert_eq!(
+                cipher
+                    .decrypt_cbc_cts(iv, &ciphertext, CtsVariant::Cs3)
+                    .unwrap(),
+                plaintext
+            );
+        }
+    }
+
+    #[test]
+    fn cbc_cts_aligned_swaps_last_blocks() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..24).collect();
+
+        let ciphertext = cipher
+            .encrypt_cbc_cts(iv, &plaintext, CtsVariant::Cs3)
+            .unwrap();
+        let cbc = cipher
+            .encrypt_cbc(iv, &plaintext, Padding::NoPadding)
+            .unwrap();
+        assert_eq!(ciphertext[..8], cbc[..8]);
+        assert_eq!(ciphertext[8..16], cbc[16..]);
+        assert_eq!(ciphertext[16..], cbc[8..16]);
+
+        assert_eq!(
+            cipher
+                .encrypt_cbc_cts(iv, &plaintext[..8], CtsVariant::Cs3)
+                .unwrap(),
+            cbc[..8]
+        );
+    }
+
+    #[test]
+    fn cbc_cts_too_short() {
+        let cipher = cipher();
+        assert_eq!(
+            cipher.encrypt_cbc_cts(Iv::new([0; 8]), &[0; 7], CtsVariant::Cs3),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+        assert_eq!(
+            cipher.decrypt_cbc_cts(Iv::new([0; 8]), &[], CtsVariant::Cs3),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn cbc_mac_last_block() {
+        let cipher = cipher();
+        let data: Vec<u8> = (0..24).collect();
+
+        let ciphertext = cipher
+            .encrypt_cbc(Iv::new([0; 8]), &data, Padding::NoPadding)
+            .unwrap();
+        assert_eq!(cipher.cbc_mac(&data).unwrap(), ciphertext[16..]);
+
+        assert_eq!(cipher.cbc_mac(&[]), Err(Rc5Error::EmptyInput));
+        assert_eq!(
+            cipher.cbc_mac(&data[..20]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn cbc_iv_not_prepended() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        let ciphertext = cipher.encrypt_cbc(iv, &[0; 8], Padding::Pkcs7).unwrap();
+        assert_eq!(ciphertext.len(), 16);
+        assert_ne!(ciphertext[..8], iv.into_bytes());
+    }
+
+    #[test]
+    fn decrypt_cbc_invalid_length() {
+        let cipher = cipher();
+        assert_eq!(
+            cipher.decrypt_cbc(Iv::new([0; 8]), &[0; 15], Padding::Pkcs7),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn cbc_cts_trace() {
+        let cipher = cipher();
+        let iv = Iv::new([0x5A; 8]);
+
+        for &len in [8usize, 9, 15, 16, 17, 24, 30].iter() {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let (blocks, out) = cipher
+                .encrypt_cbc_cts_trace(iv, &plaintext, CtsVariant::Cs3)
+                .unwrap();
+            assert_eq!(
+                out,
+                cipher
+                    .encrypt_cbc_cts(iv, &plaintext, CtsVariant::Cs3)
+                    .unwrap()
+            );
+            assert_eq!(blocks.len(), len.div_ceil(8));
+
+            // CS3: all but the last two blocks, then the last one and the truncated penultimate one
+            let n = blocks.len();
+            let tail = len - 8 * (n - 1);
+            let mut expected: Vec<u8> = blocks
+                .iter()
+                .take(n.saturating_sub(2))
+                .flatten()
+                .copied()
+                .collect();
+            if n == 1 {
+                expected.extend_from_slice(&blocks[0]);
+            } else {
+                expected.extend_from_slice(&blocks[n - 1]);
+                expected.extend_from_slice(&blocks[n - 2][..tail]);
+            }
+            assert_eq!(out, expected, "length {}", len);
+        }
+
+        assert_eq!(
+            cipher.encrypt_cbc_cts_trace(iv, &[0; 7], CtsVariant::Cs3),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn cbc_cts_variant_vectors() {
+        let cipher = cipher();
+        let iv = Iv::new([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+
+        // Self-generated regression values rather than published interop vectors: CBC blocks of
+        // the reference implementation by Rivest, ordered by hand. `cbc_crate_cts_variants` in
+        // block_cipher.rs checks the ordering against blocks of the `cbc` crate.
+        let vectors: [(usize, CtsVariant, &str); 6] = [
+            (13, CtsVariant::Cs1, "5c07cc8429b0569e06e3acd198"),
+            (13, CtsVariant::Cs2, "b0569e06e3acd1985c07cc8429"),
+            (13, CtsVariant::Cs3, "b0569e06e3acd1985c07cc8429"),
+            (16, CtsVariant::Cs1, "5c07cc8429cbb5da4b42383735856e8f"),
+            (16, CtsVariant::Cs2, "5c07cc8429cbb5da4b42383735856e8f"),
+            (16, CtsVariant::Cs3, "4b42383735856e8f5c07cc8429cbb5da"),
+        ];
+
+        for (len, variant, expected) in vectors.iter() {
+            let plaintext: Vec<u8> = (0..*len as u8).map(|i| 0x30 + i).collect();
+            let ciphertext = cipher.encrypt_cbc_cts(iv, &plaintext, *variant).unwrap();
+            assert_eq!(
+                ciphertext,
+                crate::hex::decode_hex(expected).unwrap(),
+                "{:?}, length {}",
+                variant,
+                len
+            );
+            assert_eq!(
+                cipher.decrypt_cbc_cts(iv, &ciphertext, *variant).unwrap(),
+                plaintext
+            );
+        }
+    }
+
+    #[test]
+    fn cbc_cts_variants_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for variant in [CtsVariant::Cs1, CtsVariant::Cs2, CtsVariant::Cs3].iter() {
+            for len in 8..=40 {
+                let plaintext: Vec<u8> = (0..len as u8).collect();
+                let ciphertext = cipher.encrypt_cbc_cts(iv, &plaintext, *variant).unwrap();
+
+                assert_eq!(ciphertext.len(), len);
+                assert_eq!(
+                    cipher.decrypt_cbc_cts(iv, &ciphertext, *variant).unwrap(),
+                    plaintext
+                );
+            }
+
+            // A single block is plain CBC for all variants
+            assert_eq!(
+                cipher.encrypt_cbc_cts(iv, &[0x42; 8], *variant).unwrap(),
+                cipher.encrypt_block([0x42 ^ 0xA5; 8])
+            );
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use alloc::vec::Vec;
+
+use crate::{xor_in_place, Iv, Rc5, BLOCK_BYTES};
+
+/**
+ * Cipher feedback (CFB) modes
+ *
+ * The feedback register starts out as the IV and is encrypted to produce keystream. CFB-64 feeds
+ * back whole ciphertext blocks, CFB-8 shifts a single ciphertext byte into the register at a
+ * time. Neither applies padding, the output is as long as the input.
+ */
+impl Rc5 {
+    /**
+     * Encrypt data in CFB mode with 64-bit feedback
+     */
+    pub fn encrypt_cfb64(&self, iv: Iv, data: &[u8]) -> Vec<u8> {
+        let mut out = data.to_vec();
+        let mut register = iv.into_bytes();
+
+        for chunk in out.chunks_mut(BLOCK_BYTES) {
+            let keystream = self.encrypt_block(register);
+            xor_in_place(chunk, &keystream[..chunk.len()]);
+
+            register[..chunk.len()].copy_from_slice(chunk);
+        }
+
+        out
+    }
+
+    /**
+     * Decrypt data in CFB mode with 64-bit feedback
+     */
+    pub fn decrypt_cfb64(&self, iv: Iv, data: &[u8]) -> Vec<u8> {
+        let mut out = data.to_vec();
+        let mut register = iv.into_bytes();
+
+        for chunk in out.chunks_mut(BLOCK_BYTES) {
+            let keystream = self.encrypt_block(register);
+            register[..chunk.len()].copy_from_slice(chunk);
+
+            xor_in_place(chunk, &keystream[..chunk.len()]);
+        }
+
+        out
+    }
+
+    /**
+     * Encrypt data in CFB mode with 8-bit feedback
+     */
+    pub fn encrypt_cfb8(&self, iv: Iv, data: &[u8]) -> Vec<u8> {
+        let mut register = iv.into_bytes();
+
+        data.iter()
+            .map(|b| {
+                let c = b ^ self.encrypt_block(register)[0];
+                shift_register(&mut register, c);
+                c
+            })
+            .collect()
+    }
+
+    /**
+     * Decrypt data in CFB mode with 8-bit feedback
+     */
+    pub fn decrypt_cfb8(&self, iv: Iv, data: &[u8]) -> Vec<u8> {
+        let mut register = iv.into_bytes();
+
+        data.iter()
+            .map(|&c| {
+                let b = c ^ self.encrypt_block(register)[0];
+                shift_register(&mut register, c);
+                b
+            })
+            .collect()
+    }
+}
+
+/**
+ * Shift feedback register left by one byte, appending the given ciphertext byte
+ */
+fn shift_register(register: &mut [u8; 8], c: u8) {
+    register.rotate_left(1);
+    register[BLOCK_BYTES - 1] = c;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::TryInto;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn encrypt_cfb64_feedback() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..16).collect();
+
+        let ciphertext = cipher.encrypt_cfb64(iv, &plaintext);
+        let first = cipher.encrypt_block(iv.into_bytes());
+        let second = cipher.encrypt_block(ciphertext[..8].try_into().unwrap());
+
+        for i in 0..8 {
+            assert_eq!(ciphertext[i], plaintext[i] ^ first[i]);
+            assert_eq!(ciphertext[8 + i], plaintext[8 + i] ^ second[i]);
+        }
+    }
+
+    #[test]
+    fn encrypt_cfb8_feedback() {
+        let cipher = cipher();
+        let iv = Iv::new([0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let ciphertext = cipher.encrypt_cfb8(iv, &[0x42, 0x43]);
+        assert_eq!(
+            ciphertext[0],
+            0x42 ^ cipher.encrypt_block(iv.into_bytes())[0]
+        );
+        assert_eq!(
+            ciphertext[1],
+            0x43 ^ cipher.encrypt_block([1, 2, 3, 4, 5, 6, 7, ciphertext[0]])[0]
+        );
+    }
+
+    #[test]
+    fn cfb_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for len in 0..=24 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+
+            let ciphertext = cipher.encrypt_cfb64(iv, &plaintext);
+            assert_eq!(ciphertext.len(), len);
+            assert_eq!(cipher.decrypt_cfb64(iv, &ciphertext), plaintext);
+
+            let ciphertext = cipher.encrypt_cfb8(iv, &plaintext);
+            assert_eq!(ciphertext.len(), len);
+            assert_eq!(cipher.decrypt_cfb8(iv, &ciphertext), plaintext);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+/*
+ * Conversion between blocks, their words and integers
+ *
+ * All packing of blocks into words goes through this module.
+ */
+
+use crate::{ByteOrder, Rc5, Rc5Error, Word};
+
+/**
+ * Split an RC5-32 block into its two words, packed little-endian
+ */
+pub fn block_to_words(block: [u8; 8]) -> (u32, u32) {
+    let [a, b] = pack_block::<u32>(block, ByteOrder::LittleEndian);
+    (a, b)
+}
+
+/**
+ * Join two words into an RC5-32 block, see `block_to_words`
+ */
+pub fn words_to_block(words: (u32, u32)) -> [u8; 8] {
+    unpack_block::<u32>([words.0, words.1], ByteOrder::LittleEndian)
+}
+
+/**
+ * Single RC5-32 block, as opposed to arbitrary bytes such as keys or messages
+ *
+ * The block methods of the cipher operate on `[u8; 8]`, since they are generic over the word
+ * size. `Block` converts from and into these arrays, so that it can be passed as
+ * `cipher.encrypt_block(block.into())`. Integers and words use little-endian order like
+ * `encrypt_block_u64` and `block_to_words`.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Block(pub [u8; 8]);
+
+impl Block {
+    /**
+     * Split the block into its two words, see `block_to_words`
+     */
+    pub fn to_words(self) -> (u32, u32) {
+        block_to_words(self.0)
+    }
+
+    /**
+     * Join two words into a block, see `words_to_block`
+     */
+    pub fn from_words(words: (u32, u32)) -> Block {
+        Block(words_to_block(words))
+    }
+}
+
+impl From<[u8; 8]> for Block {
+    fn from(bytes: [u8; 8]) -> Block {
+        Block(bytes)
+    }
+}
+
+impl From<Block> for [u8; 8] {
+    fn from(block: Block) -> [u8; 8] {
+        block.0
+    }
+}
+
+impl From<u64> for Block {
+    fn from(value: u64) -> Block {
+        Block(value.to_le_bytes())
+    }
+}
+
+impl From<Block> for u64 {
+    fn from(block: Block) -> u64 {
+        u64::from_le_bytes(block.0)
+    }
+}
+
+impl AsRef<[u8]> for Block {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for Block {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+/**
+ * RC5-32 with keys and blocks given as integers
+ *
+ * Integers are converted to bytes in little-endian order, i.e. the least significant byte comes
+ * first. Combined with the default byte order of the cipher, the low 32 bits of a block form its
+ * first word A.
+ */
+impl Rc5 {
+    /**
+     * Create cipher for a 16-byte key given as `key.to_le_bytes()`
+     */
+    pub fn from_u128_key(key: u128) -> Result<Rc5, Rc5Error> {
+        Rc5::new(&key.to_le_bytes())
+    }
+
+    /**
+     * Encrypt a block given as `block.to_le_bytes()`
+     */
+    pub fn encrypt_block_u64(&self, block: u64) -> u64 {
+        u64::from_le_bytes(self.encrypt_block(block.to_le_bytes()))
+    }
+
+    /**
+     * Decrypt a block given as `block.to_le_bytes()`
+     */
+    pub fn decrypt_block_u64(&self, block: u64) -> u64 {
+        u64::from_le_bytes(self.decrypt_block(block.to_le_bytes()))
+    }
+}
+
+/**
+ * Convert block to its two words
+ *
+ * Words are packed in the given byte order regardless of the target's endianness. With the
+ * default little-endian order the first byte of the block is the least significant byte of the
+ * first word, which matches the reference implementation on which the published test vectors
+ * were generated.
+ */
+pub(crate) fn pack_block<W: Word>(block: W::Block, byte_order: ByteOrder) -> [W; 2] {
+    let (a, b) = block.as_ref().split_at(W::BYTES);
+    [byte_order.read_word(a), byte_order.read_word(b)]
+}
+
+/**
+ * Convert two words back to a block, see `pack_block`
+ */
+pub(crate) fn unpack_block<W: Word>(words: [W; 2], byte_order: ByteOrder) -> W::Block {
+    let mut block = W::Block::default();
+    let (a, b) = block.as_mut().split_at_mut(W::BYTES);
+    byte_order.write_word(words[0], a);
+    byte_order.write_word(words[1], b);
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    #[test]
+    fn block_words_le() {
+        let block = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        assert_eq!(block_to_words(block), (0x33221100, 0x77665544));
+        assert_eq!(words_to_block((0x33221100, 0x77665544)), block);
+    }
+
+    #[test]
+    fn integer_key_and_block() {
+        let cipher = Rc5::from_u128_key(0x0f0e0d0c_0b0a0908_07060504_03020100).unwrap();
+
+        assert_eq!(
+            cipher.encrypt_block_u64(0x77665544_33221100),
+            0x9E8B08CF_9B14DC2D
+        );
+        assert_eq!(
+            cipher.decrypt_block_u64(0x9E8B08CF_9B14DC2D),
+            0x77665544_33221100
+        );
+    }
+
+    #[test]
+    fn block_newtype() {
+        let bytes = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let block = Block::from(bytes);
+
+        assert_eq!(block, Block::from(0x77665544_33221100u64));
+        assert_eq!(u64::from(block), 0x77665544_33221100);
+        assert_eq!(<[u8; 8]>::from(block), bytes);
+        assert_eq!(block.as_ref(), &bytes[..]);
+
+        assert_eq!(block.to_words(), (0x33221100, 0x77665544));
+        assert_eq!(Block::from_words((0x33221100, 0x77665544)), block);
+
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let ciphertext = Block::from(cipher.encrypt_block(block.into()));
+        assert_eq!(
+            u64::from(ciphertext),
+            cipher.encrypt_block_u64(block.into())
+        );
+    }
+
+    #[test]
+    fn block_words_be() {
+        let block = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        assert_eq!(
+            pack_block::<u32>(block, ByteOrder::BigEndian),
+            [0x00112233, 0x44556677]
+        );
+        assert_eq!(
+            unpack_block::<u32>([0x00112233, 0x44556677], ByteOrder::BigEndian),
+            block
+        );
+    }
+}
@@ -0,0 +1,410 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{xor_in_place, Rc5, Rc5Error, BLOCK_BYTES};
+
+/**
+ * Counter (CTR) mode
+ *
+ * The counter block is a 64-bit counter in little-endian byte order, starting out as the nonce and
+ * incremented after every block. All eight bytes belong to the counter: keystreams for two nonces
+ * overlap if the counter of one reaches the other. With the default byte order, the low 32 bits of
+ * the counter form the first word of the block and the high 32 bits the second one.
+ *
+ * The counter never wraps around, which would repeat the keystream of the zero nonce. Data that
+ * needs more blocks than remain from the nonce up to 2^64 - 1 is rejected with
+ * `CounterExhausted`, before any of it is processed.
+ */
+impl Rc5 {
+    /**
+     * Encrypt or decrypt data of arbitrary length
+     *
+     * CTR mode XORs the data with a keystream, so the same operation serves for both directions.
+     * No padding is applied and the output is as long as the input.
+     */
+    pub fn apply_ctr(&self, nonce: [u8; 8], data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        let mut out = data.to_vec();
+        self.apply_ctr_in_place(nonce, &mut out)?;
+        Ok(out)
+    }
+
+    /**
+     * Encrypt or decrypt owned or borrowed data like `apply_ctr`
+     *
+     * Owned data, such as a `Vec` converted with `into`, is processed in place and returned
+     * without allocating. Borrowed data is copied into a new buffer first, unless it is empty, in
+     * which case it is returned as is.
+     */
+    pub fn apply_ctr_cow<'a>(
+        &self,
+        nonce: [u8; 8],
+        data: impl Into<Cow<'a, [u8]>>,
+    ) -> Result<Cow<'a, [u8]>, Rc5Error> {
+        let mut data = data.into();
+        if !data.is_empty() {
+            self.apply_ctr_in_place(nonce, data.to_mut())?;
+        }
+        Ok(data)
+    }
+
+    /**
+     * Encrypt or decrypt the buffer in place like `apply_ctr`
+     *
+     * CTR mode preserves the length, so any mutable slice can be used. The buffer is left
+     * untouched if the counter would be exhausted.
+     */
+    pub fn apply_ctr_in_place(&self, nonce: [u8; 8], buf: &mut [u8]) -> Result<(), Rc5Error> {
+        if (buf.len() as u128) > remaining_keystream(nonce) {
+            return Err(Rc5Error::CounterExhausted);
+        }
+
+        let mut counter_block = nonce;
+
+        for chunk in buf.chunks_mut(BLOCK_BYTES) {
+            let keystream = self.encrypt_block(counter_block);
+            xor_in_place(chunk, &keystream[..chunk.len()]);
+
+            increment_counter(&mut counter_block);
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * CTR keystream with random access, e.g. to decrypt a range of a large ciphertext
+ *
+ * The keystream is the one of `apply_ctr` for the same nonce. Its position starts at zero and
+ * advances with every call to `fill` or `apply`. `seek` moves it to any byte offset, from which
+ * the counter block and the offset within it are derived, so nothing before the offset needs to be
+ * generated.
+ */
+pub struct Rc5Keystream<'a> {
+    cipher: &'a Rc5,
+    nonce: [u8; 8],
+    position: u64,
+}
+
+impl<'a> Rc5Keystream<'a> {
+    /**
+     * Create keystream for `cipher` and `nonce`, positioned at its start
+     */
+    pub fn new(cipher: &'a Rc5, nonce: [u8; 8]) -> Rc5Keystream<'a> {
+        Rc5Keystream {
+            cipher,
+            nonce,
+            position: 0,
+        }
+    }
+
+    /**
+     * Move to the given byte offset of the keystream
+     */
+    pub fn seek(&mut self, byte_offset: u64) {
+        self.position = byte_offset;
+    }
+
+    /**
+     * Byte offset at which the next keystream byte is taken
+     */
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /**
+     * Overwrite the buffer with the keystream at the current position and advance it
+     *
+     * Fails with `CounterExhausted`, leaving the buffer and position untouched, if the keystream
+     * ends before the buffer is filled or the position was moved beyond its end.
+     */
+    pub fn fill(&mut self, buf: &mut [u8]) -> Result<(), Rc5Error> {
+        self.process(buf, |chunk, keystream| chunk.copy_from_slice(keystream))
+    }
+
+    /**
+     * XOR the buffer with the keystream at the current position and advance it, see `fill`
+     *
+     * This encrypts or decrypts the range of the data starting at the position.
+     */
+    pub fn apply(&mut self, buf: &mut [u8]) -> Result<(), Rc5Error> {
+        self.process(buf, xor_in_place)
+    }
+
+    fn process(&mut self, buf: &mut [u8], f: impl Fn(&mut [u8], &[u8])) -> Result<(), Rc5Error> {
+        // The position may lie beyond the last counter after seeking, even for an empty buffer
+        let remaining = remaining_keystream(self.nonce).checked_sub(u128::from(self.position));
+        let end = match (remaining, self.position.checked_add(buf.len() as u64)) {
+            (Some(remaining), Some(end)) if (buf.len() as u128) <= remaining => end,
+            _ => return Err(Rc5Error::CounterExhausted),
+        };
+
+        let block_bytes = BLOCK_BYTES as u64;
+        let mut counter = u64::from_le_bytes(self.nonce).wrapping_add(self.position / block_bytes);
+        let mut offset = (self.position % block_bytes) as usize;
+
+        let mut rest = buf;
+        while !rest.is_empty() {
+            let keystream = self.cipher.encrypt_block(counter.to_le_bytes());
+            let n = core::cmp::min(BLOCK_BYTES - offset, rest.len());
+            let (chunk, tail) = rest.split_at_mut(n);
+            f(chunk, &keystream[offset..offset + n]);
+
+            rest = tail;
+            offset = 0;
+            counter = counter.wrapping_add(1);
+        }
+
+        self.position = end;
+        Ok(())
+    }
+}
+
+/**
+ * Number of keystream bytes available from the counter block up to and including the last counter
+ */
+pub(crate) fn remaining_keystream(counter_block: [u8; 8]) -> u128 {
+    let blocks = (1u128 << 64) - u128::from(u64::from_le_bytes(counter_block));
+    blocks * BLOCK_BYTES as u128
+}
+
+/**
+ * Increment the little-endian counter block
+ *
+ * The increment after the last counter wraps around, but its counter block is never used, see
+ * `remaining_keystream`.
+ */
+pub(crate) fn increment_counter(counter_block: &mut [u8; 8]) {
+    *counter_block = u64::from_le_bytes(*counter_block)
+        .wrapping_add(1)
+        .to_le_bytes();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xor;
+
+    use alloc::vec;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn apply_ctr_keystream() {
+        let cipher = cipher();
+        let nonce = [0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x10];
+
+        let keystream = cipher.apply_ctr(nonce, &[0; 16]).unwrap();
+        assert_eq!(keystream[..8], cipher.encrypt_block(nonce));
+        assert_eq!(
+            keystream[8..],
+            cipher.encrypt_block([0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x10])
+        );
+    }
+
+    #[test]
+    fn ctr_round_trip() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+
+        for len in 0..=24 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let ciphertext = cipher.apply_ctr(nonce, &plaintext).unwrap();
+
+            assert_eq!(ciphertext.len(), len);
+            assert_eq!(cipher.apply_ctr(nonce, &ciphertext).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn ctr_in_place() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let mut buf = plaintext.clone();
+        cipher.apply_ctr_in_place(nonce, &mut buf).unwrap();
+        assert_eq!(buf, cipher.apply_ctr(nonce, &plaintext).unwrap());
+
+        cipher.apply_ctr_in_place(nonce, &mut buf).unwrap();
+        assert_eq!(buf, plaintext);
+    }
+
+    #[test]
+    fn counter_carries_into_high_word() {
+        let mut counter_block = [0xFF, 0xFF, 0xFF, 0xFF, 0x01, 0x02, 0x03, 0x04];
+        increment_counter(&mut counter_block);
+        assert_eq!(
+            counter_block,
+            [0x00, 0x00, 0x00, 0x00, 0x02, 0x02, 0x03, 0x04]
+        );
+        assert_eq!(
+            crate::convert::block_to_words(counter_block),
+            (0, 0x04030202)
+        );
+    }
+
+    #[test]
+    fn counter_exhausted_at_boundary() {
+        let cipher = cipher();
+        let start = u64::MAX - 1;
+
+        // The last two counters are still available, including a partial last block
+        let keystream = cipher.apply_ctr(start.to_le_bytes(), &[0; 16]).unwrap();
+        for (i, block) in keystream.chunks_exact(8).enumerate() {
+            assert_eq!(
+                block,
+                cipher.encrypt_block_u64(start + i as u64).to_le_bytes(),
+                "block {}",
+                i
+            );
+        }
+        assert_eq!(
+            cipher.apply_ctr(start.to_le_bytes(), &[0; 9]).unwrap(),
+            keystream[..9]
+        );
+
+        // A third block would wrap around and reuse the keystream of the zero nonce
+        assert_eq!(
+            cipher.apply_ctr(start.to_le_bytes(), &[0; 17]),
+            Err(Rc5Error::CounterExhausted)
+        );
+
+        let mut buf = [0xAA; 9];
+        assert_eq!(
+            cipher.apply_ctr_in_place(u64::MAX.to_le_bytes(), &mut buf),
+            Err(Rc5Error::CounterExhausted)
+        );
+        assert_eq!(buf, [0xAA; 9]);
+    }
+
+    #[test]
+    fn remaining_keystream_bytes() {
+        assert_eq!(remaining_keystream([0; 8]), 8 << 64);
+        assert_eq!(remaining_keystream(u64::MAX.to_le_bytes()), 8);
+        assert_eq!(remaining_keystream((u64::MAX - 1).to_le_bytes()), 16);
+    }
+
+    #[test]
+    fn ctr_partial_last_block() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+
+        for blocks in 0..3 {
+            let keystream = cipher.apply_ctr(nonce, &vec![0; 8 * (blocks + 1)]).unwrap();
+
+            for tail in 1..8 {
+                let len = 8 * blocks + tail;
+                let plaintext: Vec<u8> = (0..len).map(|i| 0x30 + i as u8).collect();
+                let ciphertext = cipher.apply_ctr(nonce, &plaintext).unwrap();
+
+                // Only the first `tail` bytes of the last keystream block are used
+                let expected = xor(&plaintext, &keystream[..len]);
+                assert_eq!(ciphertext, expected, "length {}", len);
+                assert_eq!(cipher.apply_ctr(nonce, &ciphertext).unwrap(), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn ctr_cow() {
+        let cipher = cipher();
+        let nonce = [0x5A; 8];
+        let plaintext: Vec<u8> = (0..21).collect();
+        let expected = cipher.apply_ctr(nonce, &plaintext).unwrap();
+
+        let owned = plaintext.clone();
+        let ptr = owned.as_ptr();
+        let result = cipher.apply_ctr_cow(nonce, owned).unwrap();
+        assert!(matches!(result, Cow::Owned(_)));
+        assert_eq!(result.as_ptr(), ptr);
+        assert_eq!(result, expected);
+
+        assert_eq!(
+            cipher.apply_ctr_cow(nonce, &plaintext[..]).unwrap(),
+            expected
+        );
+        assert!(matches!(
+            cipher.apply_ctr_cow(nonce, &[][..]).unwrap(),
+            Cow::Borrowed(&[])
+        ));
+    }
+
+    #[test]
+    fn keystream_seek_matches_prefix() {
+        let cipher = cipher();
+        let nonce = [0x5A; 8];
+        let full = cipher.apply_ctr(nonce, &[0; 64]).unwrap();
+
+        for offset in [0, 1, 7, 8, 9, 23, 40].iter() {
+            for len in [0, 1, 8, 13, 24].iter() {
+                let mut keystream = Rc5Keystream::new(&cipher, nonce);
+                keystream.seek(*offset as u64);
+
+                let mut buf = vec![0xAA; *len];
+                keystream.fill(&mut buf).unwrap();
+                assert_eq!(buf, full[*offset..offset + len], "offset {}", offset);
+                assert_eq!(keystream.position(), (offset + len) as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn keystream_apply_decrypts_range() {
+        let cipher = cipher();
+        let nonce = [0x5A; 8];
+        let plaintext: Vec<u8> = (0..100).collect();
+        let ciphertext = cipher.apply_ctr(nonce, &plaintext).unwrap();
+
+        let mut keystream = Rc5Keystream::new(&cipher, nonce);
+        keystream.seek(37);
+        let mut range = ciphertext[37..61].to_vec();
+        keystream.apply(&mut range).unwrap();
+        assert_eq!(range, plaintext[37..61]);
+
+        // Consecutive calls continue where the previous one stopped
+        let mut range = ciphertext[61..].to_vec();
+        keystream.apply(&mut range).unwrap();
+        assert_eq!(range, plaintext[61..]);
+    }
+
+    #[test]
+    fn keystream_exhausted() {
+        let cipher = cipher();
+        let mut keystream = Rc5Keystream::new(&cipher, u64::MAX.to_le_bytes());
+        keystream.seek(5);
+
+        let mut buf = [0xAA; 4];
+        assert_eq!(keystream.fill(&mut buf), Err(Rc5Error::CounterExhausted));
+        assert_eq!(buf, [0xAA; 4]);
+        assert_eq!(keystream.position(), 5);
+
+        keystream.fill(&mut buf[..3]).unwrap();
+        assert_eq!(buf[..3], cipher.encrypt_block([0xFF; 8])[5..]);
+
+        let mut keystream = Rc5Keystream::new(&cipher, [0; 8]);
+        keystream.seek(u64::MAX);
+        assert_eq!(keystream.fill(&mut [0; 2]), Err(Rc5Error::CounterExhausted));
+    }
+
+    #[test]
+    fn keystream_seek_past_end() {
+        let cipher = cipher();
+        let mut keystream = Rc5Keystream::new(&cipher, u64::MAX.to_le_bytes());
+
+        // The end of the keystream itself is a valid position for empty buffers
+        keystream.seek(8);
+        keystream.fill(&mut []).unwrap();
+
+        keystream.seek(100);
+        let mut buf = [0xAA; 8];
+        assert_eq!(keystream.fill(&mut buf), Err(Rc5Error::CounterExhausted));
+        assert_eq!(keystream.apply(&mut buf), Err(Rc5Error::CounterExhausted));
+        assert_eq!(keystream.fill(&mut []), Err(Rc5Error::CounterExhausted));
+        assert_eq!(keystream.apply(&mut []), Err(Rc5Error::CounterExhausted));
+        assert_eq!(buf, [0xAA; 8]);
+        assert_eq!(keystream.position(), 100);
+    }
+}
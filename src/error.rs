@@ -0,0 +1,141 @@
+/**
+ * Errors reported by the cipher
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rc5Error {
+    /** Key is longer than the `expected` maximum number of bytes */
+    InvalidKeyLength { got: usize, expected: usize },
+
+    /** Key does not have the `expected` length of a parsed variant name */
+    KeyLengthMismatch { got: usize, expected: usize },
+
+    /** Word size of a parsed variant name does not match the `expected` number of bits */
+    InvalidWordSize { got: usize, expected: usize },
+
+    /** Number of rounds exceeds the `expected` maximum */
+    InvalidRounds { got: usize, expected: usize },
+
+    /** Key table does not consist of the `expected` number of words */
+    InvalidKeyTableLength { got: usize, expected: usize },
+
+    /** Key is all zeros or has fewer distinct bytes than required by `new_checked` */
+    WeakKey,
+
+    /** Variant name is not of the form RC5-w/r/b */
+    InvalidSpec,
+
+    /** No key was supplied to `Rc5Builder` */
+    MissingKey,
+
+    /** Hex string has odd length or contains non-hex characters */
+    InvalidHex,
+
+    /** Base64 string is malformed */
+    Encoding,
+
+    /** Input is empty, although the padding scheme always produces at least one block */
+    EmptyInput,
+
+    /** Input does not consist of whole blocks */
+    InvalidBlockLength,
+
+    /** Padding of decrypted data is malformed */
+    InvalidPadding,
+
+    /** Record is truncated or its length field does not match its value */
+    InvalidRecord,
+
+    /** Data exceeds the keystream left before the CTR counter would wrap around */
+    CounterExhausted,
+
+    /** Authentication tag does not match the ciphertext */
+    AuthenticationFailed,
+}
+
+impl core::fmt::Display for Rc5Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Rc5Error::InvalidKeyLength { got, expected } => {
+                write!(f, "key has {} bytes, expected at most {}", got, expected)
+            }
+            Rc5Error::KeyLengthMismatch { got, expected } => {
+                write!(f, "key has {} bytes, expected {}", got, expected)
+            }
+            Rc5Error::InvalidWordSize { got, expected } => {
+                write!(f, "word size is {} bits, expected {}", got, expected)
+            }
+            Rc5Error::InvalidRounds { got, expected } => {
+                write!(f, "{} rounds exceed maximum of {}", got, expected)
+            }
+            Rc5Error::InvalidKeyTableLength { got, expected } => {
+                write!(f, "key table has {} words, expected {}", got, expected)
+            }
+            Rc5Error::WeakKey => f.write_str("key is all zeros or has too few distinct bytes"),
+            Rc5Error::InvalidSpec => f.write_str("variant name is not of the form RC5-w/r/b"),
+            Rc5Error::MissingKey => f.write_str("no key was supplied"),
+            Rc5Error::InvalidHex => f.write_str("invalid hex string"),
+            Rc5Error::Encoding => f.write_str("invalid Base64 string"),
+            Rc5Error::EmptyInput => f.write_str("input is empty"),
+            Rc5Error::InvalidBlockLength => f.write_str("input does not consist of whole blocks"),
+            Rc5Error::InvalidPadding => f.write_str("invalid padding"),
+            Rc5Error::InvalidRecord => f.write_str("record is truncated or has a wrong length"),
+            Rc5Error::CounterExhausted => f.write_str("CTR counter exhausted"),
+            Rc5Error::AuthenticationFailed => f.write_str("authentication failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Rc5Error {}
+
+/**
+ * Report cipher errors as `InvalidData`, apart from `CounterExhausted`, which is due to the amount
+ * of input rather than its contents and therefore `InvalidInput`
+ */
+#[cfg(feature = "std")]
+impl From<Rc5Error> for std::io::Error {
+    fn from(err: Rc5Error) -> std::io::Error {
+        let kind = match err {
+            Rc5Error::CounterExhausted => std::io::ErrorKind::InvalidInput,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: 255
+            }
+            .to_string(),
+            "key has 256 bytes, expected at most 255"
+        );
+        assert_eq!(Rc5Error::InvalidPadding.to_string(), "invalid padding");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn into_io_error() {
+        use std::io;
+
+        let err = io::Error::from(Rc5Error::InvalidPadding);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "invalid padding");
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref::<Rc5Error>(),
+            Some(&Rc5Error::InvalidPadding)
+        );
+
+        let err = io::Error::from(Rc5Error::CounterExhausted);
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
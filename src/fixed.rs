@@ -0,0 +1,198 @@
+use crate::{
+    check_key_length, decode_pair, encode_pair, expand_key, key_words_len, pack_block,
+    unpack_block, ByteOrder, Rc5Error,
+};
+
+/** Number of words of the longest supported key */
+const MAX_KEY_WORDS: usize = 64;
+
+/** Number of words in the key table for `MAX_ROUNDS`, i.e. 2 * (255 + 1) */
+const MAX_KEY_TABLE_WORDS: usize = 512;
+
+/**
+ * RC5-32 with the number of rounds `R` fixed at compile time
+ *
+ * The key table is stored inline as the two whitening words followed by `R` pairs of round keys,
+ * which sizes it as 2 * (R + 1) words without const generic arithmetic. As the number of rounds
+ * is a constant, the compiler can unroll the round loop. `R` must not exceed `MAX_ROUNDS`, which is
+ * checked when compiling `new`. Bytes are packed into words little-endian.
+ */
+pub struct Rc5Gen<const R: usize> {
+    whitening: [u32; 2],
+    round_keys: [[u32; 2]; R],
+}
+
+/**
+ * RC5-32/12 with the number of rounds fixed at compile time
+ */
+pub type Rc5Default = Rc5Gen<12>;
+
+/**
+ * RC5-32/12 with a fixed-size key table, kept as another name for `Rc5Default`
+ *
+ * Unlike `Rc5`, the key table is stored inline, so constructing the cipher does not allocate.
+ */
+pub type Rc5Fixed = Rc5Default;
+
+/**
+ * Wipe the key table when the cipher goes out of scope
+ */
+#[cfg(feature = "zeroize")]
+impl<const R: usize> Drop for Rc5Gen<R> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.whitening);
+        zeroize::Zeroize::zeroize(&mut self.round_keys);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<const R: usize> zeroize::ZeroizeOnDrop for Rc5Gen<R> {}
+
+impl<const R: usize> Rc5Gen<R> {
+    /**
+     * Create cipher for the given secret key
+     */
+    pub fn new(key: &[u8]) -> Result<Rc5Gen<R>, Rc5Error> {
+        const {
+            assert!(
+                R < MAX_KEY_TABLE_WORDS / 2,
+                "number of rounds exceeds MAX_ROUNDS"
+            )
+        };
+        check_key_length(key)?;
+
+        // The key is expanded into a contiguous table as large as needed for any number of rounds
+        let mut key_words = [0; MAX_KEY_WORDS];
+        let mut key_table = [0; MAX_KEY_TABLE_WORDS];
+        let key_table_len = 2 * (R + 1);
+        expand_key(
+            key,
+            ByteOrder::LittleEndian,
+            &mut key_words[..key_words_len::<u32>(key.len())],
+            &mut key_table[..key_table_len],
+        );
+
+        let mut round_keys = [[0; 2]; R];
+        round_keys
+            .as_flattened_mut()
+            .copy_from_slice(&key_table[2..key_table_len]);
+        let whitening = [key_table[0], key_table[1]];
+
+        // Both scratch buffers are derived from the secret key and no longer needed
+        #[cfg(feature = "zeroize")]
+        {
+            zeroize::Zeroize::zeroize(&mut key_words);
+            zeroize::Zeroize::zeroize(&mut key_table);
+        }
+
+        Ok(Rc5Gen {
+            whitening,
+            round_keys,
+        })
+    }
+
+    /**
+     * Encrypt a single 8-byte block
+     */
+    pub fn encrypt_block(&self, block: [u8; 8]) -> [u8; 8] {
+        let [a, b] = pack_block::<u32>(block, ByteOrder::LittleEndian);
+        let state = (
+            a.wrapping_add(self.whitening[0]),
+            b.wrapping_add(self.whitening[1]),
+        );
+        let (a, b) = self
+            .round_keys
+            .iter()
+            .fold(state, |state, &[s0, s1]| encode_pair(state, s0, s1));
+
+        unpack_block::<u32>([a, b], ByteOrder::LittleEndian)
+    }
+
+    /**
+     * Decrypt a single 8-byte block
+     */
+    pub fn decrypt_block(&self, block: [u8; 8]) -> [u8; 8] {
+        let [a, b] = pack_block::<u32>(block, ByteOrder::LittleEndian);
+        let (a, b) = self
+            .round_keys
+            .iter()
+            .rev()
+            .fold((a, b), |state, &[s0, s1]| decode_pair(state, s0, s1));
+
+        unpack_block::<u32>(
+            [
+                a.wrapping_sub(self.whitening[0]),
+                b.wrapping_sub(self.whitening[1]),
+            ],
+            ByteOrder::LittleEndian,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{key_table_words, Rc5, MAX_KEY_BYTES, MAX_ROUNDS};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn key_table_size() {
+        assert_eq!(MAX_KEY_WORDS, key_words_len::<u32>(MAX_KEY_BYTES));
+        assert_eq!(MAX_KEY_TABLE_WORDS, key_table_words(MAX_ROUNDS));
+    }
+
+    #[test]
+    fn fixed_matches_rc5() {
+        for len in [0, 1, 10, 16, 32, 255].iter() {
+            let key: Vec<u8> = (0..*len as u8).collect();
+            let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+            let fixed = Rc5Fixed::new(&key).unwrap();
+            let cipher = Rc5::new(&key).unwrap();
+
+            let ciphertext = fixed.encrypt_block(plaintext);
+            assert_eq!(ciphertext, cipher.encrypt_block(plaintext));
+            assert_eq!(fixed.decrypt_block(ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn fixed_key_too_long() {
+        assert!(Rc5Fixed::new(&[0; 256]).is_err());
+    }
+
+    fn check_gen<const R: usize>() {
+        for len in [0, 1, 16, 255].iter() {
+            let key: Vec<u8> = (0..*len as u8).collect();
+            let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+            let gen = Rc5Gen::<R>::new(&key).unwrap();
+            let cipher = Rc5::with_rounds(&key, R).unwrap();
+
+            let ciphertext = gen.encrypt_block(plaintext);
+            assert_eq!(ciphertext, cipher.encrypt_block(plaintext), "rounds {}", R);
+            assert_eq!(gen.decrypt_block(ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn gen_matches_rc5() {
+        check_gen::<0>();
+        check_gen::<1>();
+        check_gen::<12>();
+        check_gen::<20>();
+        check_gen::<255>();
+    }
+
+    #[test]
+    fn default_gen_vector() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5Default::new(&key).unwrap();
+        assert_eq!(
+            cipher.encrypt_block([0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77]),
+            [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E]
+        );
+        assert!(Rc5Default::new(&[0; 256]).is_err());
+    }
+}
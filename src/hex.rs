@@ -0,0 +1,128 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Rc5Cipher, Rc5Error, Word};
+
+/**
+ * Hex string convenience functions, matching the way test vectors are usually presented
+ */
+impl<W: Word> Rc5Cipher<W> {
+    /**
+     * Create cipher from a hex-encoded key using `DEFAULT_ROUNDS`
+     */
+    pub fn from_hex_key(key: &str) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::new(&decode_hex(key)?)
+    }
+
+    /**
+     * Encrypt a single hex-encoded block, returning lowercase hex
+     */
+    pub fn encrypt_block_hex(&self, block: &str) -> Result<String, Rc5Error> {
+        let mut block = decode_block::<W>(block)?;
+        self.encrypt_block_in_place(&mut block);
+        Ok(encode_hex(block.as_ref()))
+    }
+
+    /**
+     * Decrypt a single hex-encoded block, returning lowercase hex
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_block_hex(&self, block: &str) -> Result<String, Rc5Error> {
+        let mut block = decode_block::<W>(block)?;
+        self.decrypt_block_in_place(&mut block);
+        Ok(encode_hex(block.as_ref()))
+    }
+}
+
+/**
+ * Parse a hex-encoded block of exactly `2 * W::BYTES` bytes
+ */
+fn decode_block<W: Word>(hex: &str) -> Result<W::Block, Rc5Error> {
+    let bytes = decode_hex(hex)?;
+
+    let mut block = W::Block::default();
+    if bytes.len() != block.as_ref().len() {
+        return Err(Rc5Error::InvalidBlockLength);
+    }
+
+    block.as_mut().copy_from_slice(&bytes);
+    Ok(block)
+}
+
+/**
+ * Parse an even-length hex string, accepting both cases
+ */
+pub(crate) fn decode_hex(hex: &str) -> Result<Vec<u8>, Rc5Error> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(Rc5Error::InvalidHex);
+    }
+
+    hex.as_bytes()
+        .chunks_exact(2)
+        .map(|pair| Ok(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+fn nibble(c: u8) -> Result<u8, Rc5Error> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(Rc5Error::InvalidHex),
+    }
+}
+
+/**
+ * Format bytes as lowercase hex
+ */
+fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    bytes
+        .iter()
+        .flat_map(|b| [DIGITS[(b >> 4) as usize], DIGITS[(b & 0xF) as usize]])
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rc5;
+
+    #[test]
+    fn encrypt_block_hex_a() {
+        let cipher = Rc5::from_hex_key("000102030405060708090A0B0C0D0E0F").unwrap();
+
+        assert_eq!(
+            cipher.encrypt_block_hex("0011223344556677").unwrap(),
+            "2ddc149bcf088b9e"
+        );
+        assert_eq!(
+            cipher.decrypt_block_hex("2DDC149BCF088B9E").unwrap(),
+            "0011223344556677"
+        );
+    }
+
+    #[test]
+    fn hex_errors() {
+        assert_eq!(Rc5::from_hex_key("012").err(), Some(Rc5Error::InvalidHex));
+        assert_eq!(Rc5::from_hex_key("0g").err(), Some(Rc5Error::InvalidHex));
+
+        let cipher = Rc5::from_hex_key("00").unwrap();
+        assert_eq!(
+            cipher.encrypt_block_hex("00112233"),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+        assert_eq!(
+            cipher.encrypt_block_hex("00112233445566zz"),
+            Err(Rc5Error::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode_hex(&encode_hex(&bytes)).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,550 @@
+/*
+ * Streaming adapters for `std::io`
+ */
+
+use core::convert::TryInto;
+use std::io::{self, Read, Write};
+
+use crate::ctr::{increment_counter, remaining_keystream};
+use crate::padding::{pad, unpad};
+use crate::{Iv, Mode, Padding, Rc5, Rc5Error, BLOCK_BYTES};
+
+/** Number of bytes requested from the inner reader at a time */
+const READ_CHUNK_BYTES: usize = 512;
+
+/**
+ * Writer that CBC-encrypts everything written to it
+ *
+ * Whole blocks are encrypted and passed on to the inner writer as soon as they are complete.
+ * The remaining partial block is buffered until `finish` applies PKCS#7 padding.
+ */
+pub struct Rc5CbcWriter<'a, W: Write> {
+    cipher: &'a Rc5,
+    inner: W,
+    prev: [u8; 8],
+    buf: Vec<u8>,
+}
+
+impl<'a, W: Write> Rc5CbcWriter<'a, W> {
+    /**
+     * Create writer encrypting into `inner`
+     */
+    pub fn new(cipher: &'a Rc5, iv: Iv, inner: W) -> Rc5CbcWriter<'a, W> {
+        Rc5CbcWriter {
+            cipher,
+            inner,
+            prev: iv.into_bytes(),
+            buf: Vec::with_capacity(BLOCK_BYTES),
+        }
+    }
+
+    /**
+     * Pad and encrypt the final block, returning the inner writer
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        let padded = pad(&self.buf, Padding::Pkcs7)?;
+
+        let mut block: [u8; 8] = padded[..].try_into().unwrap();
+        self.cipher.encrypt_cbc_block(&mut self.prev, &mut block);
+        self.inner.write_all(&block)?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+}
+
+impl<'a, W: Write> Write for Rc5CbcWriter<'a, W> {
+    /**
+     * Encrypt the whole blocks of the buffered and the given data and write them
+     *
+     * The blocks are encrypted into a separate buffer, so that the state is left untouched and
+     * `data` is not consumed if the inner writer fails.
+     */
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let mut out = Vec::with_capacity(self.buf.len() + data.len());
+        out.extend_from_slice(&self.buf);
+        out.extend_from_slice(data);
+
+        let whole = out.len() - out.len() % BLOCK_BYTES;
+        let mut prev = self.prev;
+        for chunk in out[..whole].chunks_exact_mut(BLOCK_BYTES) {
+            self.cipher
+                .encrypt_cbc_block(&mut prev, chunk.try_into().unwrap());
+        }
+
+        self.inner.write_all(&out[..whole])?;
+        self.prev = prev;
+        self.buf = out.split_off(whole);
+
+        Ok(data.len())
+    }
+
+    /**
+     * Flush the inner writer
+     *
+     * A buffered partial block is only written by `finish`, since padding can only be applied
+     * at the end of the stream.
+     */
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/**
+ * Reader that CBC-decrypts everything read from the inner reader
+ *
+ * The most recently decrypted block is held back until the inner reader reaches EOF, so that its
+ * PKCS#7 padding can be stripped.
+ */
+pub struct Rc5CbcReader<'a, R: Read> {
+    cipher: &'a Rc5,
+    inner: R,
+    prev: [u8; 8],
+    ciphertext: Vec<u8>,
+    held: Option<[u8; 8]>,
+    plaintext: Vec<u8>,
+    eof: bool,
+    error: Option<Rc5Error>,
+}
+
+impl<'a, R: Read> Rc5CbcReader<'a, R> {
+    /**
+     * Create reader decrypting from `inner`
+     */
+    pub fn new(cipher: &'a Rc5, iv: Iv, inner: R) -> Rc5CbcReader<'a, R> {
+        Rc5CbcReader {
+            cipher,
+            inner,
+            prev: iv.into_bytes(),
+            ciphertext: Vec::new(),
+            held: None,
+            plaintext: Vec::new(),
+            eof: false,
+            error: None,
+        }
+    }
+
+    /**
+     * Return the inner reader
+     */
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /**
+     * Read from the inner reader until plaintext is available or EOF is reached
+     *
+     * A malformed final block is reported again on every later call, rather than as EOF.
+     */
+    fn fill(&mut self) -> io::Result<()> {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+
+        while self.plaintext.is_empty() && !self.eof {
+            let mut chunk = [0; READ_CHUNK_BYTES];
+            let n = self.inner.read(&mut chunk)?;
+
+            if n == 0 {
+                self.eof = true;
+                return self.finish().map_err(|err| {
+                    self.error = Some(err);
+                    err.into()
+                });
+            }
+
+            self.ciphertext.extend_from_slice(&chunk[..n]);
+
+            let whole = self.ciphertext.len() - self.ciphertext.len() % BLOCK_BYTES;
+            for chunk in self.ciphertext[..whole].chunks_exact(BLOCK_BYTES) {
+                let mut block: [u8; 8] = chunk.try_into().unwrap();
+                self.cipher.decrypt_cbc_block(&mut self.prev, &mut block);
+
+                if let Some(held) = self.held.replace(block) {
+                    self.plaintext.extend_from_slice(&held);
+                }
+            }
+            self.ciphertext.drain(..whole);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Strip padding from the final block
+     */
+    fn finish(&mut self) -> Result<(), Rc5Error> {
+        if !self.ciphertext.is_empty() {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let held = self.held.take().ok_or(Rc5Error::EmptyInput)?;
+        let unpadded = unpad(&held, Padding::Pkcs7)?;
+        self.plaintext.extend_from_slice(unpadded);
+
+        Ok(())
+    }
+}
+
+impl<'a, R: Read> Read for Rc5CbcReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.fill()?;
+
+        let n = core::cmp::min(buf.len(), self.plaintext.len());
+        buf[..n].copy_from_slice(&self.plaintext[..n]);
+        self.plaintext.drain(..n);
+
+        Ok(n)
+    }
+}
+
+/**
+ * Reader that CTR-encrypts or CTR-decrypts everything read from the inner reader
+ *
+ * The keystream is generated one block at a time as data is consumed, so the counter only
+ * depends on the number of bytes read so far, not on how the inner reader splits them up.
+ *
+ * Once the counter is exhausted, reading fails with `InvalidInput` instead of wrapping around. The
+ * reader never reads more from the inner reader than it can process, except for a single byte at
+ * the end of the keystream to tell EOF apart from excess data.
+ */
+pub struct Rc5CtrReader<'a, R: Read> {
+    cipher: &'a Rc5,
+    inner: R,
+    counter_block: [u8; 8],
+    keystream: [u8; 8],
+    used: usize,
+    remaining: u128,
+}
+
+impl<'a, R: Read> Rc5CtrReader<'a, R> {
+    /**
+     * Create reader applying the keystream for `nonce` to `inner`, see `Rc5::apply_ctr`
+     */
+    pub fn new(cipher: &'a Rc5, nonce: [u8; 8], inner: R) -> Rc5CtrReader<'a, R> {
+        Rc5CtrReader {
+            cipher,
+            inner,
+            counter_block: nonce,
+            keystream: [0; 8],
+            used: BLOCK_BYTES,
+            remaining: remaining_keystream(nonce),
+        }
+    }
+
+    /**
+     * Return the inner reader
+     */
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<'a, R: Read> Read for Rc5CtrReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let limit = core::cmp::min(buf.len() as u128, self.remaining) as usize;
+        if limit == 0 && !buf.is_empty() {
+            // Data that exactly fills the keystream is fine, as long as nothing follows it
+            return match self.inner.read(&mut [0; 1])? {
+                0 => Ok(0),
+                _ => Err(Rc5Error::CounterExhausted.into()),
+            };
+        }
+
+        let n = self.inner.read(&mut buf[..limit])?;
+        self.remaining -= n as u128;
+
+        for b in buf[..n].iter_mut() {
+            if self.used == BLOCK_BYTES {
+                self.keystream = self.cipher.encrypt_block(self.counter_block);
+                increment_counter(&mut self.counter_block);
+                self.used = 0;
+            }
+
+            *b ^= self.keystream[self.used];
+            self.used += 1;
+        }
+
+        Ok(n)
+    }
+}
+
+impl Rc5 {
+    /**
+     * Encrypt the input in the given mode and write the ciphertext to `out`
+     *
+     * An exhausted CTR counter is reported as `InvalidInput`.
+     */
+    pub fn encrypt_to_writer<W: Write>(
+        &self,
+        mode: Mode,
+        iv: Iv,
+        input: &[u8],
+        out: &mut W,
+    ) -> io::Result<()> {
+        out.write_all(&self.encrypt_with_mode(mode, iv, input)?)
+    }
+
+    /**
+     * Read the ciphertext from `input` until EOF and decrypt it in the given mode
+     *
+     * Malformed ciphertext in ECB or CBC mode is reported as `InvalidData`, an exhausted CTR
+     * counter as `InvalidInput`.
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_from_reader<R: Read>(
+        &self,
+        mode: Mode,
+        iv: Iv,
+        input: &mut R,
+    ) -> io::Result<Vec<u8>> {
+        let mut ciphertext = Vec::new();
+        input.read_to_end(&mut ciphertext)?;
+
+        Ok(self.decrypt_with_mode(mode, iv, &ciphertext)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    /**
+     * Reader returning at most `limit` bytes per call
+     */
+    struct ShortReader<'a> {
+        data: &'a [u8],
+        limit: usize,
+    }
+
+    impl<'a> Read for ShortReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = core::cmp::min(core::cmp::min(buf.len(), self.limit), self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn cbc_writer_matches_encrypt_cbc() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..100).collect();
+
+        let mut writer = Rc5CbcWriter::new(&cipher, iv, Vec::new());
+        for chunk in plaintext.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        let ciphertext = writer.finish().unwrap();
+
+        assert_eq!(
+            ciphertext,
+            cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap()
+        );
+    }
+
+    /**
+     * Writer failing on its first call
+     */
+    struct FailOnceWriter {
+        failed: bool,
+        out: Vec<u8>,
+    }
+
+    impl Write for FailOnceWriter {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            if !self.failed {
+                self.failed = true;
+                return Err(io::Error::other("write failed"));
+            }
+
+            self.out.extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn cbc_writer_retry_after_error() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let inner = FailOnceWriter {
+            failed: false,
+            out: Vec::new(),
+        };
+        let mut writer = Rc5CbcWriter::new(&cipher, iv, inner);
+        assert_eq!(writer.write(&plaintext[..3]).unwrap(), 3);
+        assert!(writer.write(&plaintext[3..]).is_err());
+
+        // Nothing was consumed by the failed call, so writing the same data again is correct
+        assert_eq!(writer.write(&plaintext[3..]).unwrap(), 18);
+        let ciphertext = writer.finish().unwrap().out;
+        assert_eq!(
+            ciphertext,
+            cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap()
+        );
+    }
+
+    #[test]
+    fn cbc_reader_matches_decrypt_cbc() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for len in [0, 1, 8, 100, 2000].iter() {
+            let plaintext: Vec<u8> = (0..*len).map(|i| i as u8).collect();
+            let ciphertext = cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap();
+
+            for limit in [1, 5, 8, 1000].iter() {
+                let inner = ShortReader {
+                    data: &ciphertext,
+                    limit: *limit,
+                };
+
+                let mut result = Vec::new();
+                Rc5CbcReader::new(&cipher, iv, inner)
+                    .read_to_end(&mut result)
+                    .unwrap();
+                assert_eq!(result, plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn ctr_reader_matches_apply_ctr() {
+        let cipher = cipher();
+        let nonce = [0xA5; 8];
+        let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+
+        for limit in [1, 3, 8, 13, 1000].iter() {
+            let inner = ShortReader {
+                data: &data,
+                limit: *limit,
+            };
+
+            let mut result = Vec::new();
+            Rc5CtrReader::new(&cipher, nonce, inner)
+                .read_to_end(&mut result)
+                .unwrap();
+            assert_eq!(result, cipher.apply_ctr(nonce, &data).unwrap());
+        }
+    }
+
+    #[test]
+    fn ctr_reader_counter_exhausted() {
+        let cipher = cipher();
+        let nonce = u64::MAX.to_le_bytes();
+        let data = [0x5A; 12];
+        let mut inner = &data[..];
+
+        let mut reader = Rc5CtrReader::new(&cipher, nonce, &mut inner);
+        let mut buf = [0; 12];
+        assert_eq!(reader.read(&mut buf).unwrap(), 8);
+        assert_eq!(buf[..8], cipher.apply_ctr(nonce, &data[..8]).unwrap()[..]);
+
+        let err = reader.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(reader.read(&mut []).unwrap(), 0);
+
+        // Apart from the byte read to detect them, the excess bytes are left in the inner reader
+        assert_eq!(inner, &data[9..]);
+    }
+
+    #[test]
+    fn ctr_reader_fills_keystream_exactly() {
+        let cipher = cipher();
+        let nonce = u64::MAX.to_le_bytes();
+        let data = [0x5A; 8];
+
+        let mut reader = Rc5CtrReader::new(&cipher, nonce, &data[..]);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, cipher.apply_ctr(nonce, &data).unwrap());
+    }
+
+    #[test]
+    fn cbc_reader_invalid_length() {
+        let cipher = cipher();
+        let ciphertext = cipher
+            .encrypt_cbc(Iv::new([0; 8]), &[1, 2, 3], Padding::Pkcs7)
+            .unwrap();
+
+        let mut reader = Rc5CbcReader::new(&cipher, Iv::new([0; 8]), &ciphertext[..5]);
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn cbc_reader_error_persists() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let ciphertext = cipher.encrypt_cbc(iv, &[9; 8], Padding::NoPadding).unwrap();
+
+        let mut reader = Rc5CbcReader::new(&cipher, iv, &ciphertext[..]);
+        for _ in 0..2 {
+            let err = reader.read(&mut [0; 16]).unwrap_err();
+            let err = err.get_ref().unwrap().downcast_ref::<Rc5Error>();
+            assert_eq!(err, Some(&Rc5Error::InvalidPadding));
+        }
+    }
+
+    #[test]
+    fn mode_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let modes = [Mode::Ecb, Mode::Cbc, Mode::Ctr, Mode::Cfb, Mode::Ofb];
+        let expected = [
+            cipher.encrypt_ecb(&plaintext, Padding::Pkcs7).unwrap(),
+            cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap(),
+            cipher.apply_ctr(iv.into_bytes(), &plaintext).unwrap(),
+            cipher.encrypt_cfb64(iv, &plaintext),
+            cipher.apply_ofb(iv, &plaintext),
+        ];
+
+        for (mode, expected) in modes.iter().zip(expected.iter()) {
+            let mut ciphertext = Vec::new();
+            cipher
+                .encrypt_to_writer(*mode, iv, &plaintext, &mut ciphertext)
+                .unwrap();
+            assert_eq!(ciphertext, *expected, "{:?}", mode);
+
+            let decrypted = cipher
+                .decrypt_from_reader(*mode, iv, &mut &ciphertext[..])
+                .unwrap();
+            assert_eq!(decrypted, plaintext, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn decrypt_from_reader_invalid() {
+        let cipher = cipher();
+
+        for mode in [Mode::Ecb, Mode::Cbc].iter() {
+            for ciphertext in [&[][..], &[0; 12]].iter() {
+                let err = cipher
+                    .decrypt_from_reader(*mode, Iv::new([0; 8]), &mut &ciphertext[..])
+                    .unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            }
+        }
+
+        assert_eq!(
+            cipher
+                .decrypt_from_reader(Mode::Ctr, Iv::new([0; 8]), &mut &[][..])
+                .unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+}
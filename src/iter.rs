@@ -0,0 +1,132 @@
+/*
+ * Iterator adapters applying the cipher to a stream of blocks
+ */
+
+use crate::{Rc5Cipher, Word};
+
+/**
+ * Lazy ECB encryption of blocks, see `BlockIteratorExt::encrypt_ecb`
+ */
+#[derive(Clone)]
+pub struct EncryptEcb<'a, W: Word, I> {
+    cipher: &'a Rc5Cipher<W>,
+    blocks: I,
+}
+
+/**
+ * Lazy ECB decryption of blocks, see `BlockIteratorExt::decrypt_ecb`
+ */
+#[derive(Clone)]
+pub struct DecryptEcb<'a, W: Word, I> {
+    cipher: &'a Rc5Cipher<W>,
+    blocks: I,
+}
+
+impl<'a, W: Word, I: Iterator<Item = W::Block>> Iterator for EncryptEcb<'a, W, I> {
+    type Item = W::Block;
+
+    fn next(&mut self) -> Option<W::Block> {
+        self.blocks
+            .next()
+            .map(|block| self.cipher.encrypt_block(block))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.blocks.size_hint()
+    }
+}
+
+impl<'a, W: Word, I: Iterator<Item = W::Block>> Iterator for DecryptEcb<'a, W, I> {
+    type Item = W::Block;
+
+    fn next(&mut self) -> Option<W::Block> {
+        self.blocks
+            .next()
+            .map(|block| self.cipher.decrypt_block(block))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.blocks.size_hint()
+    }
+}
+
+/**
+ * Extension of block iterators with adapters that encrypt or decrypt each block
+ *
+ * Blocks are processed independently as in ECB mode and only when requested, so that the
+ * adapters can be placed anywhere in an iterator pipeline.
+ */
+pub trait BlockIteratorExt: Iterator + Sized {
+    /**
+     * Encrypt every block with `cipher`
+     */
+    fn encrypt_ecb<W>(self, cipher: &Rc5Cipher<W>) -> EncryptEcb<'_, W, Self>
+    where
+        W: Word,
+        Self: Iterator<Item = W::Block>,
+    {
+        EncryptEcb {
+            cipher,
+            blocks: self,
+        }
+    }
+
+    /**
+     * Decrypt every block with `cipher`
+     */
+    fn decrypt_ecb<W>(self, cipher: &Rc5Cipher<W>) -> DecryptEcb<'_, W, Self>
+    where
+        W: Word,
+        Self: Iterator<Item = W::Block>,
+    {
+        DecryptEcb {
+            cipher,
+            blocks: self,
+        }
+    }
+}
+
+impl<I: Iterator> BlockIteratorExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+    use crate::Rc5;
+
+    #[test]
+    fn adapters_match_block_methods() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let blocks: Vec<[u8; 8]> = (0..5).map(|i| [i; 8]).collect();
+
+        let encrypted = blocks.iter().copied().encrypt_ecb(&cipher);
+        assert_eq!(encrypted.size_hint(), (5, Some(5)));
+
+        let ciphertext: Vec<[u8; 8]> = encrypted.clone().collect();
+        assert_eq!(
+            ciphertext,
+            cipher.encrypt_blocks(&blocks).collect::<Vec<_>>()
+        );
+
+        let plaintext: Vec<[u8; 8]> = encrypted.decrypt_ecb(&cipher).collect();
+        assert_eq!(plaintext, blocks);
+    }
+
+    #[test]
+    fn adapters_rc5_64() {
+        let cipher = Rc5Cipher::<u64>::new(&[0x42; 24]).unwrap();
+        let blocks = [[1; 16], [2; 16]];
+
+        let ciphertext: Vec<[u8; 16]> = blocks.iter().copied().encrypt_ecb(&cipher).collect();
+        assert_eq!(ciphertext[0], cipher.encrypt_block([1; 16]));
+        assert_eq!(
+            ciphertext
+                .into_iter()
+                .decrypt_ecb(&cipher)
+                .collect::<Vec<_>>(),
+            blocks
+        );
+    }
+}
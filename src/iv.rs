@@ -0,0 +1,83 @@
+/*
+ * Initialisation vectors for the CBC, CFB and OFB modes
+ */
+
+#[cfg(feature = "rand")]
+use rand_core::RngCore;
+
+/**
+ * Initialisation vector of one block
+ *
+ * CBC, CFB and OFB require a fresh, unpredictable IV for every message encrypted with the same
+ * key. Reusing an IV leaks which messages share a common prefix, in OFB even their XOR. With the
+ * `rand` feature, `Iv::random` draws IVs from a caller-provided generator. The mode functions
+ * take an `Iv` rather than bare bytes, so that fixed IVs stand out as `Iv::new`.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Iv([u8; 8]);
+
+impl Iv {
+    /**
+     * IV with the given bytes
+     */
+    pub const fn new(bytes: [u8; 8]) -> Iv {
+        Iv(bytes)
+    }
+
+    /**
+     * Draw a fresh IV from `rng`, which should be a cryptographically secure generator
+     */
+    #[cfg(feature = "rand")]
+    pub fn random<R: RngCore>(rng: &mut R) -> Iv {
+        let mut bytes = [0; 8];
+        rng.fill_bytes(&mut bytes);
+        Iv(bytes)
+    }
+
+    /**
+     * Bytes of the IV
+     */
+    pub fn into_bytes(self) -> [u8; 8] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for Iv {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod tests {
+    use super::*;
+
+    /** Generator returning a fixed sequence of words */
+    struct SequenceRng(u64);
+
+    impl RngCore for SequenceRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for chunk in dst.chunks_mut(8) {
+                let bytes = self.next_u64().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+    }
+
+    #[test]
+    fn random_iv() {
+        let mut rng = SequenceRng(0);
+
+        assert_eq!(Iv::random(&mut rng).into_bytes(), 1u64.to_le_bytes());
+        assert_eq!(Iv::random(&mut rng), Iv::new(2u64.to_le_bytes()));
+    }
+}
@@ -1,133 +1,731 @@
 /*
- * RC5 for 32-bit words based on the C implementation by Rivest (1997) [1]
+ * RC5 based on the C implementation by Rivest (1997) [1]
+ *
+ * The cipher is generic over the word size w, see `Word`. `Rc5` is the classic 32-bit variant.
+ *
+ * The crate is `no_std` when the default `std` feature is disabled and then only requires `alloc`.
  *
  * [1] https://www.grc.com/r&d/rc5.pdf
  */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+extern crate alloc;
+
+#[cfg(feature = "aead")]
+mod aead;
+#[cfg(feature = "base64")]
+mod base64;
+#[cfg(feature = "cipher")]
+pub mod block_cipher;
+mod builder;
+mod byte_order;
+mod cbc;
+mod cfb;
+pub mod convert;
+mod ctr;
+mod ecb;
+mod error;
+mod fixed;
+mod hex;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+mod iv;
+#[cfg(test)]
+mod kat;
+#[cfg(feature = "message")]
+mod message;
+mod mode;
+mod ofb;
+pub mod padding;
+mod params;
+#[cfg(feature = "passphrase")]
+mod passphrase;
+#[cfg(test)]
+mod proptests;
+pub mod rc6;
+#[cfg(test)]
+mod reference;
+#[cfg(feature = "ct-study")]
+pub mod round;
+#[cfg(not(feature = "ct-study"))]
+mod round;
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "simd")]
+mod simd;
 mod util;
-use util::ArithExt;
-use util::CollectRev;
+mod word;
+#[cfg(feature = "aead")]
+pub use aead::Rc5EtmCtr;
+pub use builder::Rc5Builder;
+pub use byte_order::ByteOrder;
+pub use cbc::CtsVariant;
+pub use convert::Block;
+use convert::{pack_block, unpack_block};
+pub use ctr::Rc5Keystream;
+pub use error::Rc5Error;
+pub use fixed::{Rc5Default, Rc5Fixed, Rc5Gen};
+#[cfg(feature = "std")]
+pub use io::{Rc5CbcReader, Rc5CbcWriter, Rc5CtrReader};
+pub use iter::{BlockIteratorExt, DecryptEcb, EncryptEcb};
+pub use iv::Iv;
+pub use mode::{BlockMode, Cbc, Ctr, Ecb, Mode};
+pub use padding::Padding;
+pub use params::CipherParams;
+#[cfg(feature = "passphrase")]
+pub use passphrase::DEFAULT_PBKDF2_ITERATIONS;
+use round::{decode_pair, encode_pair};
+use util::partial_word;
+pub use util::{bytes_to_words, ct_eq, words_to_bytes, xor, xor_in_place};
+use word::{default_key_table, DEFAULT_KEY_TABLE_WORDS};
+pub use word::{magic_constants, Word};
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/** Number of rounds used by `new` */
+pub static DEFAULT_ROUNDS: usize = 12;
+
+/** Maximum number of rounds permitted by the specification */
+pub static MAX_ROUNDS: usize = 255;
+
+/** Maximum key length in bytes permitted by the specification */
+pub static MAX_KEY_BYTES: usize = 255;
 
-static WORD_SIZE_BITS: usize = 32;
-static WORD_SIZE_BYTES: usize = WORD_SIZE_BITS / 8;
+/** Minimum number of distinct key bytes required by `new_checked` */
+pub static MIN_DISTINCT_KEY_BYTES: usize = 4;
+
+/** Block size of `Rc5` in bytes */
+const BLOCK_BYTES: usize = 8;
+
+/**
+ * Number of words in the expanded key table
+ */
+fn key_table_words(rounds: usize) -> usize {
+    2 * (rounds + 1)
+}
+
+/**
+ * Check the number of rounds against `MAX_ROUNDS`
+ */
+fn check_rounds(rounds: usize) -> Result<(), Rc5Error> {
+    if rounds > MAX_ROUNDS {
+        return Err(Rc5Error::InvalidRounds {
+            got: rounds,
+            expected: MAX_ROUNDS,
+        });
+    }
+
+    Ok(())
+}
 
 /**
- * Magic constants
+ * RC5 cipher with a cached key table
  *
- * Note: These constants are word-size dependent, see section 4.3.
+ * The key table is expanded once on construction and reused for every block. The word type
+ * selects the variant, e.g. `Rc5Cipher<u16>` for RC5-16 or `Rc5Cipher<u64>` for RC5-64.
+ *
+ * The cipher is `Send` and `Sync`, so a single instance can be shared between threads. Cloning
+ * instead gives every thread its own instance without expanding the key again. A clone is a deep
+ * copy of the key table, which is derived from the secret key: every clone is another copy of key
+ * material in memory that needs to be dropped (and with `zeroize`, gets wiped).
  */
-static P: u32 = 0xb7e15163;
-static Q: u32 = 0x9e3779b9;
+#[derive(Clone)]
+pub struct Rc5Cipher<W: Word> {
+    rounds: usize,
+    key_len: Option<usize>,
+    key_table: Vec<W>,
+    byte_order: ByteOrder,
+    wiped: bool,
+}
 
-static ROUNDS: usize = 12;
+/**
+ * RC5 with 32-bit words, i.e. 8-byte blocks
+ */
+pub type Rc5 = Rc5Cipher<u32>;
 
-static KEY_BYTES: usize = 16;
+/**
+ * Wipe the key table when the cipher goes out of scope
+ */
+#[cfg(feature = "zeroize")]
+impl<W: Word> Drop for Rc5Cipher<W> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.key_table);
+    }
+}
 
-static KEY_TABLE_WORDS: usize = 2 * (ROUNDS + 1);
+#[cfg(feature = "zeroize")]
+impl<W: Word> zeroize::ZeroizeOnDrop for Rc5Cipher<W> {}
+
+impl<W: Word> Rc5Cipher<W> {
+    /**
+     * Create cipher for the given secret key using `DEFAULT_ROUNDS`
+     *
+     * The key may have up to `MAX_KEY_BYTES` (255) bytes. The number of key words is derived from
+     * its length, so that every byte influences the key table. The specification also permits an
+     * empty key, which is expanded like a single zero word. Such a cipher works, but is
+     * cryptographically useless since anyone can reproduce its key table.
+     */
+    pub fn new(key: &[u8]) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::with_rounds(key, DEFAULT_ROUNDS)
+    }
+
+    /**
+     * Create cipher like `new`, rejecting keys that look like placeholders
+     *
+     * See `new_checked_with_min_distinct`, using `MIN_DISTINCT_KEY_BYTES`.
+     */
+    pub fn new_checked(key: &[u8]) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::new_checked_with_min_distinct(key, MIN_DISTINCT_KEY_BYTES)
+    }
+
+    /**
+     * Create cipher like `new`, failing with `WeakKey` if the key is all zeros or consists of
+     * fewer than `min_distinct` distinct bytes
+     *
+     * Keys shorter than `min_distinct` must consist of distinct bytes only. This is a safeguard
+     * against accidentally using a placeholder key such as `[0; 16]` or `b"aaaaaaaa"`, not a
+     * measure of key strength. A key that passes may still be weak.
+     */
+    pub fn new_checked_with_min_distinct(
+        key: &[u8],
+        min_distinct: usize,
+    ) -> Result<Rc5Cipher<W>, Rc5Error> {
+        check_weak_key(key, min_distinct)?;
+        Rc5Cipher::new(key)
+    }
+
+    /**
+     * Create cipher for the given secret key and number of rounds
+     */
+    pub fn with_rounds(key: &[u8], rounds: usize) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::with_byte_order(key, rounds, ByteOrder::default())
+    }
+
+    /**
+     * Create cipher for the given secret key, number of rounds and packing of bytes into words
+     */
+    pub fn with_byte_order(
+        key: &[u8],
+        rounds: usize,
+        byte_order: ByteOrder,
+    ) -> Result<Rc5Cipher<W>, Rc5Error> {
+        check_rounds(rounds)?;
+
+        Ok(Rc5Cipher {
+            rounds,
+            key_len: Some(key.len()),
+            key_table: key_table(key, rounds, byte_order)?,
+            byte_order,
+            wiped: false,
+        })
+    }
+
+    /**
+     * Create cipher from a key table previously obtained by `expanded_key`, using `DEFAULT_ROUNDS`
+     */
+    pub fn from_key_table(key_table: Vec<W>) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::from_key_table_with_rounds(key_table, DEFAULT_ROUNDS)
+    }
+
+    /**
+     * Create cipher from a key table for the given number of rounds
+     *
+     * The table must consist of 2r + 2 words.
+     */
+    pub fn from_key_table_with_rounds(
+        key_table: Vec<W>,
+        rounds: usize,
+    ) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::from_parts(key_table, rounds, ByteOrder::default())
+    }
+
+    /**
+     * Create cipher from a key table, validating its length
+     */
+    pub(crate) fn from_parts(
+        key_table: Vec<W>,
+        rounds: usize,
+        byte_order: ByteOrder,
+    ) -> Result<Rc5Cipher<W>, Rc5Error> {
+        check_rounds(rounds)?;
+
+        if key_table.len() != key_table_words(rounds) {
+            return Err(Rc5Error::InvalidKeyTableLength {
+                got: key_table.len(),
+                expected: key_table_words(rounds),
+            });
+        }
+
+        Ok(Rc5Cipher {
+            rounds,
+            key_len: None,
+            key_table,
+            byte_order,
+            wiped: false,
+        })
+    }
+
+    /**
+     * Number of rounds
+     */
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /**
+     * Length of the secret key in bytes, unknown if the cipher was created from a key table
+     */
+    pub fn key_len(&self) -> Option<usize> {
+        self.key_len
+    }
+
+    /**
+     * Packing of bytes into words
+     */
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /**
+     * Expanded key table S of 2r + 2 words, e.g. 26 words for RC5-32/12
+     */
+    pub fn expanded_key(&self) -> &[W] {
+        &self.key_table
+    }
+
+    /**
+     * Overwrite the key table with zeros, making the cipher unusable
+     *
+     * Encrypting or decrypting afterwards panics in debug builds and produces meaningless output
+     * otherwise.
+     */
+    pub fn wipe(&mut self) {
+        for word in self.key_table.iter_mut() {
+            *word = W::default();
+        }
+        self.wiped = true;
+    }
+
+    /**
+     * Whether the key table was cleared by `wipe`
+     */
+    pub fn is_wiped(&self) -> bool {
+        self.wiped
+    }
+
+    /**
+     * Replace the secret key, keeping the number of rounds and byte order
+     *
+     * The key table is rebuilt in place, reusing its allocation. Its previous contents are
+     * overwritten during the expansion. On error, the cipher is left unchanged.
+     */
+    pub fn rekey(&mut self, key: &[u8]) -> Result<(), Rc5Error> {
+        check_key_length(key)?;
+
+        let mut key_words = vec![W::default(); key_words_len::<W>(key.len())];
+        expand_key(key, self.byte_order, &mut key_words, &mut self.key_table);
+        self.key_len = Some(key.len());
+
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut key_words);
+
+        self.wiped = false;
+        Ok(())
+    }
+
+    /**
+     * Encrypt a single block
+     */
+    pub fn encrypt_block(&self, mut block: W::Block) -> W::Block {
+        self.encrypt_block_in_place(&mut block);
+        block
+    }
+
+    /**
+     * Decrypt a single block
+     */
+    pub fn decrypt_block(&self, mut block: W::Block) -> W::Block {
+        self.decrypt_block_in_place(&mut block);
+        block
+    }
+
+    /**
+     * Lazily encrypt a slice of whole blocks, equivalent to ECB mode without padding
+     */
+    pub fn encrypt_blocks<'a>(
+        &'a self,
+        blocks: &'a [W::Block],
+    ) -> impl Iterator<Item = W::Block> + 'a {
+        blocks.iter().map(move |block| self.encrypt_block(*block))
+    }
+
+    /**
+     * Lazily decrypt a slice of whole blocks, see `encrypt_blocks`
+     */
+    pub fn decrypt_blocks<'a>(
+        &'a self,
+        blocks: &'a [W::Block],
+    ) -> impl Iterator<Item = W::Block> + 'a {
+        blocks.iter().map(move |block| self.decrypt_block(*block))
+    }
+
+    /**
+     * Apply round `round` of the encryption to the two words of the state
+     *
+     * Round 0 is the initial key whitening, which adds S[0] and S[1]. Rounds 1 to r are the
+     * actual rounds, so applying rounds 0 to r in order to the packed plaintext yields the
+     * ciphertext. Panics if `round` exceeds the number of rounds.
+     */
+    pub fn encrypt_round(&self, state: (W, W), round: usize) -> (W, W) {
+        assert!(round <= self.rounds, "round out of range");
+        encode_round(&self.key_table, state, round)
+    }
+
+    /**
+     * Invert round `round` of the encryption, see `encrypt_round`
+     *
+     * Decryption applies rounds r down to 0.
+     */
+    pub fn decrypt_round(&self, state: (W, W), round: usize) -> (W, W) {
+        assert!(round <= self.rounds, "round out of range");
+        decode_round(&self.key_table, state, round)
+    }
+
+    /**
+     * Encrypt a single block, recording the state after every round
+     *
+     * The trace holds r + 1 entries, the first one after the key whitening of round 0, see
+     * `encrypt_round`. The last one consists of the words of the ciphertext.
+     */
+    pub fn encrypt_block_trace(&self, block: W::Block) -> (Vec<(W, W)>, W::Block) {
+        let [a, b] = pack_block::<W>(block, self.byte_order);
+
+        let mut trace = Vec::with_capacity(self.rounds + 1);
+        let mut state = (a, b);
+        for round in 0..=self.rounds {
+            state = self.encrypt_round(state, round);
+            trace.push(state);
+        }
+
+        (
+            trace,
+            unpack_block::<W>([state.0, state.1], self.byte_order),
+        )
+    }
+
+    /**
+     * Encrypt a single block, overwriting it with the ciphertext
+     */
+    pub fn encrypt_block_in_place(&self, block: &mut W::Block) {
+        debug_assert!(!self.wiped, "cipher was wiped");
+        *block = unpack_block::<W>(
+            encode(
+                &self.key_table,
+                self.rounds,
+                pack_block::<W>(*block, self.byte_order),
+            ),
+            self.byte_order,
+        );
+    }
+
+    /**
+     * Decrypt a single block, overwriting it with the plaintext
+     */
+    pub fn decrypt_block_in_place(&self, block: &mut W::Block) {
+        debug_assert!(!self.wiped, "cipher was wiped");
+        *block = unpack_block::<W>(
+            decode(
+                &self.key_table,
+                self.rounds,
+                pack_block::<W>(*block, self.byte_order),
+            ),
+            self.byte_order,
+        );
+    }
+
+    /**
+     * Encrypt consecutive pairs of words as blocks in ECB mode, reusing the vector
+     *
+     * Each pair holds the words A and B of a block, so no conversion from bytes is involved and
+     * the byte order of the cipher is irrelevant. Fails with `InvalidBlockLength` if the number of
+     * words is odd.
+     */
+    pub fn encrypt_vec(&self, mut words: Vec<W>) -> Result<Vec<W>, Rc5Error> {
+        self.apply_pairs(&mut words, encode)?;
+        Ok(words)
+    }
+
+    /**
+     * Decrypt consecutive pairs of words, see `encrypt_vec`
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_vec(&self, mut words: Vec<W>) -> Result<Vec<W>, Rc5Error> {
+        self.apply_pairs(&mut words, decode)?;
+        Ok(words)
+    }
+
+    fn apply_pairs(
+        &self,
+        words: &mut [W],
+        f: fn(&[W], usize, [W; 2]) -> [W; 2],
+    ) -> Result<(), Rc5Error> {
+        if !words.len().is_multiple_of(2) {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        debug_assert!(!self.wiped, "cipher was wiped");
+        for pair in words.chunks_exact_mut(2) {
+            let [a, b] = f(&self.key_table, self.rounds, [pair[0], pair[1]]);
+            pair[0] = a;
+            pair[1] = b;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Print the cipher parameters only, the key table is redacted
+ */
+impl<W: Word> core::fmt::Debug for Rc5Cipher<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Rc5Cipher")
+            .field("rounds", &self.rounds)
+            .field("word_bits", &W::BITS)
+            .field("key_len", &self.key_len)
+            .field("byte_order", &self.byte_order)
+            .field("key_table", &format_args!("[redacted]"))
+            .finish()
+    }
+}
+
+/**
+ * Create cipher for the given secret key using `DEFAULT_ROUNDS`, see `Rc5Cipher::new`
+ */
+impl<W: Word> TryFrom<&[u8]> for Rc5Cipher<W> {
+    type Error = Rc5Error;
+
+    fn try_from(key: &[u8]) -> Result<Rc5Cipher<W>, Rc5Error> {
+        Rc5Cipher::new(key)
+    }
+}
 
 /**
- * Number of words in key
+ * Encrypt a single block with RC5-32/12
  *
- * max(1, ceil(8 * KEY_BYTES / WORD_SIZE_BITS))
+ * The key schedule is recomputed on every call. Use `Rc5` to encrypt more than one block with the
+ * same key.
  */
-static KEY_WORDS: usize = 4;
+pub fn encrypt(key: &[u8], block: [u8; 8]) -> Result<[u8; 8], Rc5Error> {
+    Ok(Rc5::new(key)?.encrypt_block(block))
+}
+
+/**
+ * Decrypt a single block with RC5-32/12
+ *
+ * The key schedule is recomputed on every call. Use `Rc5` to decrypt more than one block with the
+ * same key.
+ */
+pub fn decrypt(key: &[u8], block: [u8; 8]) -> Result<[u8; 8], Rc5Error> {
+    Ok(Rc5::new(key)?.decrypt_block(block))
+}
 
 /**
  * Expand key table
  */
-fn key_table(key: &Vec<u8>) -> Vec<u32> {
-    assert_eq!(key.len(), KEY_BYTES);
+fn key_table<W: Word>(
+    key: &[u8],
+    rounds: usize,
+    byte_order: ByteOrder,
+) -> Result<Vec<W>, Rc5Error> {
+    key_table_with_len(key, key_table_words(rounds), byte_order)
+}
 
-    // Step 1: Convert secret key from bytes to words
-    let mut key_iter = key.iter().rev();
-    let mut key_words: Vec<u32> = (0..KEY_WORDS)
-        .map(|_| (0..WORD_SIZE_BYTES).fold(0, |v, _| (v << 8) + (*key_iter.next().unwrap() as u32)))
-        .collect_rev();
+/**
+ * Expand key into a key table of the given number of words
+ */
+fn key_table_with_len<W: Word>(
+    key: &[u8],
+    len: usize,
+    byte_order: ByteOrder,
+) -> Result<Vec<W>, Rc5Error> {
+    check_key_length(key)?;
 
-    // Step 2: Initialise key table to fixed key-independent pseudo-random bit pattern. This is
-    // achieved by an arithmetic progression that makes use of the magic onstants P and Q.
-    let mut key_table: Vec<u32> = (0..KEY_TABLE_WORDS)
-        .scan(0, |v, i| {
-            *v = if i == 0 { P } else { (*v).platform_add(Q) };
-            Some(*v)
-        })
-        .collect();
+    let mut key_words = vec![W::default(); key_words_len::<W>(key.len())];
+    let mut key_table = vec![W::default(); len];
+    expand_key(key, byte_order, &mut key_words, &mut key_table);
+
+    // The key words are derived from the secret key and no longer needed
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut key_words);
+
+    Ok(key_table)
+}
+
+/**
+ * Ensure key length is within the limits of the specification
+ */
+fn check_key_length(key: &[u8]) -> Result<(), Rc5Error> {
+    if key.len() > MAX_KEY_BYTES {
+        return Err(Rc5Error::InvalidKeyLength {
+            got: key.len(),
+            expected: MAX_KEY_BYTES,
+        });
+    }
+
+    Ok(())
+}
+
+/**
+ * Reject keys that are all zeros or have too few distinct bytes
+ */
+fn check_weak_key(key: &[u8], min_distinct: usize) -> Result<(), Rc5Error> {
+    let mut seen = [false; 256];
+    key.iter().for_each(|&b| seen[b as usize] = true);
+    let distinct = seen.iter().filter(|&&s| s).count();
 
-    // Step 3: Mix in the supplied secret key by passing over the key words and the key table three
-    // times.
-    let (mut key_table_val, mut key_words_val): (u32, u32) = (0, 0);
-    let (mut key_table_iter, mut key_words_iter) =
-        ((0..key_table.len()).cycle(), (0..key_words.len()).cycle());
+    if key.iter().all(|&b| b == 0) || distinct < core::cmp::min(min_distinct, key.len()) {
+        return Err(Rc5Error::WeakKey);
+    }
 
-    for _ in 0..3 * key_table.len() {
-        let key_table_idx = key_table_iter.next().unwrap();
-        key_table_val = key_table[key_table_idx]
-            .platform_add(key_table_val)
-            .platform_add(key_words_val)
-            .rotate_left(3);
-        let _ = std::mem::replace(&mut key_table[key_table_idx], key_table_val);
+    Ok(())
+}
 
-        let key_words_idx = key_words_iter.next().unwrap();
-        key_words_val = key_words[key_words_idx]
-            .platform_add(key_table_val)
-            .platform_add(key_words_val)
-            .rotate_left(key_table_val.platform_add(key_words_val));
-        let _ = std::mem::replace(&mut key_words[key_words_idx], key_words_val);
+/**
+ * Number of words in key: max(1, ceil(8 * b / w))
+ */
+fn key_words_len<W: Word>(key_bytes: usize) -> usize {
+    core::cmp::max(1, key_bytes.div_ceil(W::BYTES))
+}
+
+/**
+ * Expand key into the given key table
+ *
+ * The key bytes are packed into words in `byte_order`. `key_words` is used as scratch space and
+ * must hold `key_words_len` words.
+ */
+fn expand_key<W: Word>(
+    key: &[u8],
+    byte_order: ByteOrder,
+    key_words: &mut [W],
+    key_table: &mut [W],
+) {
+    // Step 1: Convert secret key from bytes to words. The final partial word is zero-padded, so
+    // that it only holds the remaining key bytes.
+    key_words.iter_mut().for_each(|w| *w = W::default());
+    for (word, chunk) in key_words.iter_mut().zip(key.chunks(W::BYTES)) {
+        *word = partial_word(chunk, byte_order);
+    }
+
+    // Step 2: Initialise key table to fixed key-independent pseudo-random bit pattern. This is
+    // achieved by an arithmetic progression that makes use of the magic onstants P and Q. For the
+    // default number of rounds, the progression is precomputed for words of up to 64 bits.
+    if key_table.len() == DEFAULT_KEY_TABLE_WORDS && W::BITS <= 64 {
+        // The table is computed for every word type, so wider words must not reach it
+        let table = const { default_key_table(if W::BITS <= 64 { W::BITS as u32 } else { 64 }) };
+        for (slot, bytes) in key_table.iter_mut().zip(table.iter()) {
+            *slot = W::from_le_bytes(&bytes[..W::BYTES]);
+        }
+    } else {
+        key_table.iter_mut().fold(W::P, |v, slot| {
+            *slot = v;
+            v.wrapping_add(W::Q)
+        });
     }
 
-    key_table
+    // Step 3: Mix in the supplied secret key by passing three times over the larger of the key
+    // words and the key table. This follows the reference implementation, where A and B carry the
+    // most recently written key table and key word values, and i and j cycle through both arrays.
+    let (t, c) = (key_table.len(), key_words.len());
+    let (mut a, mut b) = (W::default(), W::default());
+    let (mut i, mut j) = (0, 0);
+
+    for _ in 0..3 * core::cmp::max(t, c) {
+        a = key_table[i].wrapping_add(a).wrapping_add(b).rotate_left(3);
+        key_table[i] = a;
+
+        b = key_words[j]
+            .wrapping_add(a)
+            .wrapping_add(b)
+            .rotate_left(a.wrapping_add(b).as_shift());
+        key_words[j] = b;
+
+        i = (i + 1) % t;
+        j = (j + 1) % c;
+    }
 }
 
 /**
  * Return ciphertext for a given key table and plaintext
+ *
+ * The round keys are traversed as pairs, which lets the compiler drop the bounds checks.
  */
-fn encode(key_table: Vec<u32>, plaintext: Vec<u32>) -> Vec<u32> {
-    assert_eq!(key_table.len(), KEY_TABLE_WORDS);
-    assert_eq!(plaintext.len(), 2);
+fn encode<W: Word>(key_table: &[W], rounds: usize, plaintext: [W; 2]) -> [W; 2] {
+    assert_eq!(key_table.len(), key_table_words(rounds));
 
-    let initial: (u32, u32) = (
-        plaintext[0].platform_add(key_table[0]),
-        plaintext[1].platform_add(key_table[1]),
+    let (whitening, round_keys) = key_table.split_at(2);
+    let state = (
+        plaintext[0].wrapping_add(whitening[0]),
+        plaintext[1].wrapping_add(whitening[1]),
     );
+    let (a, b) = round_keys
+        .chunks_exact(2)
+        .fold(state, |state, keys| encode_pair(state, keys[0], keys[1]));
 
-    let (a, b) = (1..(ROUNDS + 1)).fold(initial, |acc, i| {
-        let (mut a, mut b) = acc;
-        a = (a ^ b).rotate_left(b).platform_add(key_table[2 * i]);
-        b = (b ^ a).rotate_left(a).platform_add(key_table[2 * i + 1]);
-        (a, b)
-    });
-
-    vec![a, b]
+    [a, b]
 }
 
 /**
  * Return plaintext for a given key table and ciphertext
  */
-fn decode(key_table: Vec<u32>, ciphertext: Vec<u32>) -> Vec<u32> {
-    assert_eq!(key_table.len(), KEY_TABLE_WORDS);
-    assert_eq!(ciphertext.len(), 2);
+fn decode<W: Word>(key_table: &[W], rounds: usize, ciphertext: [W; 2]) -> [W; 2] {
+    assert_eq!(key_table.len(), key_table_words(rounds));
+
+    let (whitening, round_keys) = key_table.split_at(2);
+    let (a, b) = round_keys
+        .chunks_exact(2)
+        .rev()
+        .fold((ciphertext[0], ciphertext[1]), |state, keys| {
+            decode_pair(state, keys[0], keys[1])
+        });
 
-    let initial: (u32, u32) = (ciphertext[0], ciphertext[1]);
+    [a.wrapping_sub(whitening[0]), b.wrapping_sub(whitening[1])]
+}
 
-    let (a, b) = (1..(ROUNDS + 1)).rev().fold(initial, |acc, i| {
-        let (mut a, mut b) = acc;
-        b = b.platform_sub(key_table[2 * i + 1]).rotate_right(a) ^ a;
-        a = a.platform_sub(key_table[2 * i]).rotate_right(b) ^ b;
-        (a, b)
-    });
+/**
+ * Apply a single round of encryption, where round 0 is the initial addition of S[0] and S[1]
+ */
+fn encode_round<W: Word>(key_table: &[W], (a, b): (W, W), round: usize) -> (W, W) {
+    if round == 0 {
+        return (a.wrapping_add(key_table[0]), b.wrapping_add(key_table[1]));
+    }
 
-    vec![a.platform_sub(key_table[0]), b.platform_sub(key_table[1])]
+    encode_pair((a, b), key_table[2 * round], key_table[2 * round + 1])
+}
+
+/**
+ * Invert a single round of encryption, see `encode_round`
+ */
+fn decode_round<W: Word>(key_table: &[W], (a, b): (W, W), round: usize) -> (W, W) {
+    if round == 0 {
+        return (a.wrapping_sub(key_table[0]), b.wrapping_sub(key_table[1]));
+    }
+
+    decode_pair((a, b), key_table[2 * round], key_table[2 * round + 1])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn to_u32(v: Vec<u8>) -> Vec<u32> {
-        let (head, body, tail) = unsafe { v.align_to::<u32>() };
-        assert!(head.is_empty());
-        assert!(tail.is_empty());
-        body.to_vec()
+    fn to_u32(v: Vec<u8>) -> [u32; 2] {
+        let words = bytes_to_words(&v);
+        [words[0], words[1]]
     }
 
     #[test]
@@ -144,7 +742,11 @@ mod tests {
             0x37a8debc, 0x5735676a, 0xf96b764a, 0x7aec5407, 0x15e8e206,
         ];
 
-        assert_eq!(key_table(&key), expected);
+        assert_eq!(
+            key_table::<u32>(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            expected
+        );
+        assert_eq!(Rc5::new(&key).unwrap().expanded_key(), &expected[..]);
     }
 
     #[test]
@@ -157,7 +759,11 @@ mod tests {
         let plaintext: Vec<u8> = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
         let ciphertext: Vec<u8> = vec![0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
 
-        let result = encode(key_table(&key), to_u32(plaintext));
+        let result = encode(
+            &key_table(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            12,
+            to_u32(plaintext),
+        );
         assert_eq!(result, to_u32(ciphertext));
     }
 
@@ -171,10 +777,30 @@ mod tests {
         let plaintext: Vec<u8> = vec![0xEA, 0x02, 0x47, 0x14, 0xAD, 0x5C, 0x4D, 0x84];
         let ciphertext: Vec<u8> = vec![0x11, 0xE4, 0x3B, 0x86, 0xD2, 0x31, 0xEA, 0x64];
 
-        let result = encode(key_table(&key), to_u32(plaintext));
+        let result = encode(
+            &key_table(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            12,
+            to_u32(plaintext),
+        );
         assert_eq!(result, to_u32(ciphertext));
     }
 
+    #[test]
+    fn encode_reuses_key_table() {
+        let key: Vec<u8> = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+
+        let key_table = key_table(&key, 12, ByteOrder::LittleEndian).unwrap();
+        let plaintext: [u32; 2] = [0x33221100, 0x77665544];
+
+        let first = encode(&key_table, 12, plaintext);
+        let second = encode(&key_table, 12, plaintext);
+        assert_eq!(first, second);
+        assert_eq!(decode(&key_table, 12, first), plaintext);
+    }
+
     #[test]
     fn decode_a() {
         let key = vec![
@@ -185,7 +811,11 @@ mod tests {
         let plaintext: Vec<u8> = vec![0x96, 0x95, 0x0D, 0xDA, 0x65, 0x4A, 0x3D, 0x62];
         let ciphertext: Vec<u8> = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
 
-        let result = decode(key_table(&key), to_u32(ciphertext));
+        let result = decode(
+            &key_table(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            12,
+            to_u32(ciphertext),
+        );
         assert_eq!(result, to_u32(plaintext));
     }
 
@@ -199,7 +829,596 @@ mod tests {
         let plaintext: Vec<u8> = vec![0x63, 0x8B, 0x3A, 0x5E, 0xF7, 0x2B, 0x66, 0x3F];
         let ciphertext: Vec<u8> = vec![0xEA, 0x02, 0x47, 0x14, 0xAD, 0x5C, 0x4D, 0x84];
 
-        let result = decode(key_table(&key), to_u32(ciphertext));
+        let result = decode(
+            &key_table(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            12,
+            to_u32(ciphertext),
+        );
         assert_eq!(result, to_u32(plaintext));
     }
+
+    #[test]
+    fn rc5_round_trip() {
+        let key: Vec<u8> = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
+
+        let cipher = Rc5::new(&key).unwrap();
+        assert_eq!(cipher.encrypt_block(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn block_in_place() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
+
+        let cipher = Rc5::new(&key).unwrap();
+        let mut block = plaintext;
+
+        cipher.encrypt_block_in_place(&mut block);
+        assert_eq!(block, ciphertext);
+
+        cipher.decrypt_block_in_place(&mut block);
+        assert_eq!(block, plaintext);
+    }
+
+    #[test]
+    fn rc5_16_round_trip() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [0x00, 0x01, 0x02, 0x03];
+
+        let cipher = Rc5Cipher::<u16>::new(&key).unwrap();
+        let ciphertext = cipher.encrypt_block(plaintext);
+        assert_ne!(ciphertext, plaintext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn rc5_64_round_trip() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+
+        let cipher = Rc5Cipher::<u64>::new(&key).unwrap();
+        let ciphertext = cipher.encrypt_block(plaintext);
+        assert_ne!(ciphertext, plaintext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn rounds_sweep() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        let ciphertexts: Vec<[u8; 8]> = (8..=20)
+            .map(|rounds| {
+                let cipher = Rc5::with_rounds(&key, rounds).unwrap();
+                assert_eq!(cipher.rounds(), rounds);
+                assert_eq!(cipher.key_table.len(), 2 * (rounds + 1));
+
+                let ciphertext = cipher.encrypt_block(plaintext);
+                assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+                ciphertext
+            })
+            .collect();
+
+        assert_eq!(
+            ciphertexts[4],
+            Rc5::new(&key).unwrap().encrypt_block(plaintext)
+        );
+    }
+
+    #[test]
+    fn max_rounds() {
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        // Ciphertexts generated with the reference implementation by Rivest
+        let vectors = [
+            (16, [0x7B, 0x49, 0x4E, 0x00, 0x25, 0x03, 0x04, 0xB3]),
+            (
+                MAX_KEY_BYTES,
+                [0x20, 0xCD, 0x24, 0xA8, 0x06, 0x46, 0x58, 0x70],
+            ),
+        ];
+
+        for (key_len, ciphertext) in vectors.iter() {
+            let key: Vec<u8> = (0..*key_len as u8).collect();
+            let cipher = Rc5::with_rounds(&key, MAX_ROUNDS).unwrap();
+            assert_eq!(cipher.expanded_key().len(), 512);
+
+            assert_eq!(cipher.encrypt_block(plaintext), *ciphertext);
+            assert_eq!(cipher.decrypt_block(*ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn empty_key() {
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        // Generated with the reference implementation by Rivest
+        let ciphertext = [0x19, 0x4C, 0x67, 0x60, 0x57, 0xC3, 0x3F, 0xBE];
+
+        let cipher = Rc5::new(&[]).unwrap();
+        assert_eq!(cipher.key_len(), Some(0));
+        assert_eq!(cipher.encrypt_block(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+
+        // The single key word is zero either way
+        for key in [&[0][..], &[0; 4]].iter() {
+            assert_eq!(Rc5::new(key).unwrap().expanded_key(), cipher.expanded_key());
+        }
+        assert_eq!(key_words_len::<u32>(0), 1);
+    }
+
+    #[test]
+    fn long_key_not_truncated() {
+        for len in [17, MAX_KEY_BYTES].iter() {
+            let key: Vec<u8> = (0..*len as u8).collect();
+            let reference = Rc5::new(&key).unwrap();
+
+            for i in 0..*len {
+                let mut modified = key.clone();
+                modified[i] ^= 1;
+                assert_ne!(
+                    Rc5::new(&modified).unwrap().expanded_key(),
+                    reference.expanded_key(),
+                    "key byte {}",
+                    i
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encrypt_vec() {
+        let cipher = Rc5::new(&(0..16).collect::<Vec<u8>>()).unwrap();
+        let blocks = [[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77], [0xAA; 8]];
+
+        let words: Vec<u32> = blocks
+            .iter()
+            .flat_map(|block| {
+                let (a, b) = convert::block_to_words(*block);
+                vec![a, b]
+            })
+            .collect();
+        let ciphertext = cipher.encrypt_vec(words.clone()).unwrap();
+
+        for (pair, block) in ciphertext.chunks_exact(2).zip(blocks.iter()) {
+            assert_eq!(
+                convert::words_to_block((pair[0], pair[1])),
+                cipher.encrypt_block(*block)
+            );
+        }
+        assert_eq!(cipher.decrypt_vec(ciphertext).unwrap(), words);
+
+        assert_eq!(cipher.encrypt_vec(vec![]), Ok(vec![]));
+        assert_eq!(
+            cipher.encrypt_vec(vec![1, 2, 3]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+        assert_eq!(
+            cipher.decrypt_vec(vec![1]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn rounds_out_of_range() {
+        let result = Rc5::with_rounds(&[0; 16], 256);
+        assert_eq!(
+            result.err(),
+            Some(Rc5Error::InvalidRounds {
+                got: 256,
+                expected: 255
+            })
+        );
+    }
+
+    #[test]
+    fn one_shot() {
+        let key: Vec<u8> = (0..16).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
+
+        assert_eq!(encrypt(&key, plaintext), Ok(ciphertext));
+        assert_eq!(decrypt(&key, ciphertext), Ok(plaintext));
+        assert!(encrypt(&[0; 256], plaintext).is_err());
+    }
+
+    #[test]
+    fn single_rounds() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        let [a, b] = pack_block::<u32>(plaintext, ByteOrder::LittleEndian);
+        let state = (0..=12).fold((a, b), |state, i| cipher.encrypt_round(state, i));
+        assert_eq!(
+            unpack_block::<u32>([state.0, state.1], ByteOrder::LittleEndian),
+            cipher.encrypt_block(plaintext)
+        );
+
+        let state = (0..=12)
+            .rev()
+            .fold(state, |state, i| cipher.decrypt_round(state, i));
+        assert_eq!(state, (a, b));
+
+        assert_eq!(
+            cipher.encrypt_round((a, b), 0),
+            (
+                a.wrapping_add(cipher.expanded_key()[0]),
+                b.wrapping_add(cipher.expanded_key()[1])
+            )
+        );
+    }
+
+    #[test]
+    fn block_trace() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        let (trace, ciphertext) = cipher.encrypt_block_trace(plaintext);
+        assert_eq!(trace.len(), 13);
+        assert_eq!(ciphertext, cipher.encrypt_block(plaintext));
+
+        let (a, b) = *trace.last().unwrap();
+        assert_eq!(
+            unpack_block::<u32>([a, b], ByteOrder::LittleEndian),
+            ciphertext
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "round out of range")]
+    fn round_out_of_range() {
+        Rc5::new(&[0; 16]).unwrap().encrypt_round((0, 0), 13);
+    }
+
+    #[test]
+    fn blocks_iterator() {
+        let cipher = Rc5::new(&[0xAB; 16]).unwrap();
+        let blocks = [[0; 8], [1; 8], [2; 8]];
+
+        let ciphertext: Vec<[u8; 8]> = cipher.encrypt_blocks(&blocks).collect();
+        let expected = cipher.encrypt_ecb(&blocks.concat(), Padding::NoPadding);
+        assert_eq!(ciphertext.concat(), expected.unwrap());
+
+        let plaintext: Vec<[u8; 8]> = cipher.decrypt_blocks(&ciphertext).collect();
+        assert_eq!(plaintext, blocks);
+    }
+
+    #[test]
+    fn wipe_clears_key_table() {
+        let mut cipher = Rc5::new(&[0xAB; 16]).unwrap();
+        assert!(!cipher.is_wiped());
+
+        cipher.wipe();
+        assert!(cipher.is_wiped());
+        assert!(cipher.expanded_key().iter().all(|&w| w == 0));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "cipher was wiped")]
+    fn wipe_prevents_use() {
+        let mut cipher = Rc5::new(&[0xAB; 16]).unwrap();
+        cipher.wipe();
+        cipher.encrypt_block([0; 8]);
+    }
+
+    #[test]
+    fn rekey() {
+        let mut cipher = Rc5::with_byte_order(&[0xAB; 16], 20, ByteOrder::BigEndian).unwrap();
+        let table = cipher.expanded_key().as_ptr();
+        cipher.wipe();
+
+        let key: Vec<u8> = (0..10).collect();
+        cipher.rekey(&key).unwrap();
+        assert!(!cipher.is_wiped());
+        assert_eq!(cipher.expanded_key().as_ptr(), table);
+
+        let fresh = Rc5::with_byte_order(&key, 20, ByteOrder::BigEndian).unwrap();
+        assert_eq!(cipher.expanded_key(), fresh.expanded_key());
+
+        assert_eq!(
+            cipher.rekey(&[0; 256]),
+            Err(Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: 255
+            })
+        );
+        assert_eq!(cipher.expanded_key(), fresh.expanded_key());
+    }
+
+    #[test]
+    fn new_checked() {
+        let key: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            Rc5::new_checked(&key).unwrap().expanded_key(),
+            Rc5::new(&key).unwrap().expanded_key()
+        );
+        assert!(Rc5::new_checked(&[1, 2]).is_ok());
+
+        for weak in [&[][..], &[0; 16], &[0xAA; 16], b"abcabcabcabc"].iter() {
+            assert_eq!(Rc5::new_checked(weak).err(), Some(Rc5Error::WeakKey));
+        }
+        assert_eq!(Rc5::new_checked(&[1, 1]).err(), Some(Rc5Error::WeakKey));
+
+        assert!(Rc5::new_checked_with_min_distinct(b"abcabcabcabc", 3).is_ok());
+        assert!(Rc5::new_checked_with_min_distinct(&[0xAA; 16], 1).is_ok());
+        assert_eq!(
+            Rc5::new_checked_with_min_distinct(&[0; 16], 0).err(),
+            Some(Rc5Error::WeakKey)
+        );
+    }
+
+    #[test]
+    fn debug_redacts_key_table() {
+        let cipher = Rc5::new(&[0xAB; 16]).unwrap();
+        let debug = alloc::format!("{:?}", cipher);
+
+        assert_eq!(
+            debug,
+            "Rc5Cipher { rounds: 12, word_bits: 32, key_len: Some(16), \
+             byte_order: LittleEndian, key_table: [redacted] }"
+        );
+        for word in cipher.expanded_key() {
+            assert!(!debug.contains(&alloc::format!("{:08x}", word)));
+            assert!(!debug.contains(&alloc::format!("{}", word)));
+        }
+    }
+
+    #[test]
+    fn cipher_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Rc5>();
+        assert_send_sync::<Rc5Cipher<u16>>();
+        assert_send_sync::<Rc5Cipher<u64>>();
+    }
+
+    #[test]
+    fn clone_encrypts_identically() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::with_byte_order(&key, 16, ByteOrder::BigEndian).unwrap();
+        let clone = cipher.clone();
+
+        assert_eq!(clone.params(), cipher.params());
+        assert_eq!(clone.byte_order(), ByteOrder::BigEndian);
+        assert_ne!(
+            clone.expanded_key().as_ptr(),
+            cipher.expanded_key().as_ptr()
+        );
+        for block in [[0; 8], [0xFF; 8], [1, 2, 3, 4, 5, 6, 7, 8]].iter() {
+            assert_eq!(clone.encrypt_block(*block), cipher.encrypt_block(*block));
+        }
+
+        drop(cipher);
+        assert_eq!(
+            clone.decrypt_block(clone.encrypt_block([0xAB; 8])),
+            [0xAB; 8]
+        );
+    }
+
+    #[test]
+    fn try_from_slice() -> Result<(), Rc5Error> {
+        use core::convert::TryInto;
+
+        let key: Vec<u8> = (0..16).collect();
+        let cipher: Rc5 = key.as_slice().try_into()?;
+        assert_eq!(
+            cipher.encrypt_block([0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77]),
+            [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E]
+        );
+
+        assert_eq!(
+            Rc5::try_from(&[0; 256][..]).err(),
+            Some(Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: 255
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_key_table_round_trip() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+
+        let restored = Rc5::from_key_table(cipher.expanded_key().to_vec()).unwrap();
+        assert_eq!(restored.rounds(), DEFAULT_ROUNDS);
+        assert_eq!(restored.encrypt_block([0; 8]), cipher.encrypt_block([0; 8]));
+
+        let cipher = Rc5::with_rounds(&key, 20).unwrap();
+        let restored = Rc5::from_key_table_with_rounds(cipher.expanded_key().to_vec(), 20).unwrap();
+        assert_eq!(restored.encrypt_block([0; 8]), cipher.encrypt_block([0; 8]));
+    }
+
+    #[test]
+    fn from_key_table_invalid_length() {
+        assert_eq!(
+            Rc5::from_key_table(vec![0; 25]).err(),
+            Some(Rc5Error::InvalidKeyTableLength {
+                got: 25,
+                expected: 26
+            })
+        );
+        assert_eq!(
+            Rc5::from_key_table_with_rounds(vec![0; 26], 20).err(),
+            Some(Rc5Error::InvalidKeyTableLength {
+                got: 26,
+                expected: 42
+            })
+        );
+    }
+
+    #[test]
+    fn from_key_table_mismatched_rounds() {
+        let table = Rc5::with_rounds(&[0xAB; 16], 16)
+            .unwrap()
+            .expanded_key()
+            .to_vec();
+
+        assert_eq!(
+            Rc5::from_key_table_with_rounds(table.clone(), 12).err(),
+            Some(Rc5Error::InvalidKeyTableLength {
+                got: 34,
+                expected: 26
+            })
+        );
+        assert_eq!(
+            Rc5::from_key_table_with_rounds(table, 16).unwrap().rounds(),
+            16
+        );
+    }
+
+    #[test]
+    fn key_length_10() {
+        let key: Vec<u8> = (0..10).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x5C, 0xA5, 0xC7, 0xD3, 0xA2, 0x5B, 0xA0, 0x0E];
+
+        let cipher = Rc5::new(&key).unwrap();
+        assert_eq!(cipher.encrypt_block(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn key_length_32() {
+        let key: Vec<u8> = (0..32).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x94, 0x9E, 0x0A, 0x77, 0xB8, 0xDB, 0xBB, 0xE3];
+
+        let cipher = Rc5::new(&key).unwrap();
+        assert_eq!(cipher.encrypt_block(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn key_longer_than_key_table() {
+        // 10 key words exceed the 4 words of the key table for a single round
+        let key: Vec<u8> = (0..40).collect();
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x5C, 0x25, 0x92, 0x5E, 0x99, 0x02, 0xBF, 0xFE];
+
+        let cipher = Rc5::with_rounds(&key, 1).unwrap();
+        assert_eq!(cipher.encrypt_block(plaintext), ciphertext);
+        assert_eq!(cipher.decrypt_block(ciphertext), plaintext);
+    }
+
+    #[test]
+    fn key_too_long() {
+        let result = Rc5::new(&[0; 256]);
+        assert_eq!(
+            result.err(),
+            Some(Rc5Error::InvalidKeyLength {
+                got: 256,
+                expected: 255
+            })
+        );
+    }
+
+    #[test]
+    fn block_packing_le() {
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ciphertext = [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
+
+        let plaintext_words = [
+            u32::from_le_bytes([0x00, 0x11, 0x22, 0x33]),
+            u32::from_le_bytes([0x44, 0x55, 0x66, 0x77]),
+        ];
+        let ciphertext_words = [
+            u32::from_le_bytes([0x2D, 0xDC, 0x14, 0x9B]),
+            u32::from_le_bytes([0xCF, 0x08, 0x8B, 0x9E]),
+        ];
+
+        assert_eq!(
+            pack_block::<u32>(plaintext, ByteOrder::LittleEndian),
+            plaintext_words
+        );
+        assert_eq!(
+            unpack_block::<u32>(ciphertext_words, ByteOrder::LittleEndian),
+            ciphertext
+        );
+
+        let key: Vec<u8> = (0..16).collect();
+        let result = encode(
+            &key_table(&key, 12, ByteOrder::LittleEndian).unwrap(),
+            12,
+            plaintext_words,
+        );
+        assert_eq!(result, ciphertext_words);
+    }
+
+    #[test]
+    fn key_and_block_packing_agree() {
+        // Key bytes and block bytes are packed into words by the same convention, which is
+        // little-endian unless selected otherwise. The reference implementation builds key words
+        // as L[i / u] = (L[i / u] << 8) + K[i] for i from b - 1 down to 0, which is the same.
+        let bytes = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        for (byte_order, words) in [
+            (ByteOrder::LittleEndian, [0x33221100, 0x77665544]),
+            (ByteOrder::BigEndian, [0x00112233, 0x44556677]),
+        ]
+        .iter()
+        {
+            let key_words: Vec<u32> = bytes
+                .chunks(4)
+                .map(|chunk| partial_word(chunk, *byte_order))
+                .collect();
+
+            assert_eq!(key_words, words);
+            assert_eq!(pack_block::<u32>(bytes, *byte_order), *words);
+        }
+
+        assert_eq!(bytes_to_words::<u32>(&bytes), [0x33221100, 0x77665544]);
+    }
+
+    #[test]
+    fn big_endian() {
+        // Derived from the reference implementation by reversing the bytes of every key and block
+        // word, which turns its little-endian packing into big-endian packing
+        let plaintext = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let vectors = [
+            (16, [0x65, 0xF9, 0x20, 0x3B, 0x76, 0x6F, 0x90, 0x2C]),
+            (10, [0x55, 0x60, 0x82, 0x5B, 0x02, 0xA3, 0x91, 0xF3]),
+            (8, [0xC1, 0x48, 0xD4, 0x01, 0xEB, 0x09, 0xC5, 0x91]),
+        ];
+
+        for (key_len, ciphertext) in vectors.iter() {
+            let key: Vec<u8> = (0..*key_len).collect();
+            let cipher = Rc5::with_byte_order(&key, 12, ByteOrder::BigEndian).unwrap();
+
+            assert_eq!(cipher.encrypt_block(plaintext), *ciphertext);
+            assert_eq!(cipher.decrypt_block(*ciphertext), plaintext);
+        }
+
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::with_byte_order(&key, 12, ByteOrder::LittleEndian).unwrap();
+        assert_eq!(
+            cipher.encrypt_block(plaintext),
+            [0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E]
+        );
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_on_drop() {
+        fn assert_zeroize_on_drop<T: zeroize::ZeroizeOnDrop>() {}
+
+        assert_zeroize_on_drop::<Rc5Cipher<u16>>();
+        assert_zeroize_on_drop::<Rc5Cipher<u32>>();
+        assert_zeroize_on_drop::<Rc5Cipher<u64>>();
+    }
 }
@@ -0,0 +1,219 @@
+/*
+ * Self-contained messages consisting of a random IV followed by the CBC ciphertext
+ *
+ * Records are messages holding a type-length-value triple: a type byte, the value length as a
+ * little-endian `u32` and the value.
+ */
+
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
+use rand_core::RngCore;
+
+use crate::{Iv, Padding, Rc5, Rc5Error, BLOCK_BYTES};
+
+/** Length of the type and length fields preceding the value of a record */
+const RECORD_HEADER_BYTES: usize = 5;
+
+impl Rc5 {
+    /**
+     * Encrypt a message with CBC and PKCS#7 padding under a fresh IV drawn from `rng`
+     *
+     * The IV is prepended to the ciphertext, so that `decrypt_message` needs nothing but the
+     * output. `rng` should be a cryptographically secure generator.
+     */
+    pub fn encrypt_message<R: RngCore>(&self, rng: &mut R, msg: &[u8]) -> Vec<u8> {
+        let iv = Iv::random(rng);
+
+        // PKCS#7 padding accepts data of any length
+        let ciphertext = self.encrypt_cbc(iv, msg, Padding::Pkcs7).unwrap();
+
+        let mut out = Vec::with_capacity(BLOCK_BYTES + ciphertext.len());
+        out.extend_from_slice(iv.as_ref());
+        out.extend_from_slice(&ciphertext);
+        out
+    }
+
+    /**
+     * Split off the IV and decrypt a message produced by `encrypt_message`
+     *
+     * Fails with `InvalidBlockLength` if the message is shorter than the IV or its ciphertext
+     * does not consist of whole blocks, with `EmptyInput` if there is no ciphertext, and with
+     * `InvalidPadding` if the padding is malformed.
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_message(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        if data.len() < BLOCK_BYTES {
+            return Err(Rc5Error::InvalidBlockLength);
+        }
+
+        let (iv, ciphertext) = data.split_at(BLOCK_BYTES);
+        let iv = Iv::new(iv.try_into().unwrap());
+        self.decrypt_cbc(iv, ciphertext, Padding::Pkcs7)
+    }
+
+    /**
+     * Encrypt a record of the given type and value as a message, see `encrypt_message`
+     *
+     * Fails with `InvalidRecord` if the value is too long for the length field.
+     */
+    pub fn encrypt_record<R: RngCore>(
+        &self,
+        rng: &mut R,
+        record_type: u8,
+        value: &[u8],
+    ) -> Result<Vec<u8>, Rc5Error> {
+        let len = u32::try_from(value.len()).map_err(|_| Rc5Error::InvalidRecord)?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_BYTES + value.len());
+        record.push(record_type);
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(value);
+
+        Ok(self.encrypt_message(rng, &record))
+    }
+
+    /**
+     * Decrypt a record produced by `encrypt_record`, returning its type and value
+     *
+     * Fails like `decrypt_message`, and with `InvalidRecord` if the decrypted data is shorter
+     * than the header or its length field does not match the length of the value.
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_record(&self, data: &[u8]) -> Result<(u8, Vec<u8>), Rc5Error> {
+        let mut record = self.decrypt_message(data)?;
+        if record.len() < RECORD_HEADER_BYTES {
+            return Err(Rc5Error::InvalidRecord);
+        }
+
+        let len = u32::from_le_bytes(record[1..RECORD_HEADER_BYTES].try_into().unwrap());
+        if len as usize != record.len() - RECORD_HEADER_BYTES {
+            return Err(Rc5Error::InvalidRecord);
+        }
+
+        let record_type = record[0];
+        record.drain(..RECORD_HEADER_BYTES);
+        Ok((record_type, record))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /** Deterministic generator producing consecutive byte values */
+    struct CountingRng(u8);
+
+    impl RngCore for CountingRng {
+        fn next_u32(&mut self) -> u32 {
+            let mut bytes = [0; 4];
+            self.fill_bytes(&mut bytes);
+            u32::from_le_bytes(bytes)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let mut bytes = [0; 8];
+            self.fill_bytes(&mut bytes);
+            u64::from_le_bytes(bytes)
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            for b in dst {
+                *b = self.0;
+                self.0 = self.0.wrapping_add(1);
+            }
+        }
+    }
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn message_layout() {
+        let cipher = cipher();
+        let msg = b"attack at dawn";
+
+        let sealed = cipher.encrypt_message(&mut CountingRng(0), msg);
+        let iv = Iv::new([0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(sealed[..8], iv.into_bytes());
+        assert_eq!(
+            sealed[8..],
+            cipher.encrypt_cbc(iv, msg, Padding::Pkcs7).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn message_round_trip() {
+        let cipher = cipher();
+        let mut rng = CountingRng(0x42);
+
+        for len in 0..=24 {
+            let msg: Vec<u8> = (0..len as u8).collect();
+            let sealed = cipher.encrypt_message(&mut rng, &msg);
+
+            assert_eq!(sealed.len(), 8 + (len / 8 + 1) * 8);
+            assert_eq!(cipher.decrypt_message(&sealed).unwrap(), msg);
+        }
+
+        // Every message uses a fresh IV
+        assert_ne!(
+            cipher.encrypt_message(&mut rng, b"msg"),
+            cipher.encrypt_message(&mut rng, b"msg")
+        );
+    }
+
+    #[test]
+    fn decrypt_message_invalid_length() {
+        let cipher = cipher();
+
+        assert_eq!(
+            cipher.decrypt_message(&[0; 7]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+        assert_eq!(cipher.decrypt_message(&[0; 8]), Err(Rc5Error::EmptyInput));
+        assert_eq!(
+            cipher.decrypt_message(&[0; 20]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let cipher = cipher();
+        let mut rng = CountingRng(0);
+
+        for len in [0, 1, 3, 11, 100].iter() {
+            let value: Vec<u8> = (0..*len as u8).collect();
+            let sealed = cipher.encrypt_record(&mut rng, 0x17, &value).unwrap();
+
+            assert_eq!(sealed.len(), 8 + ((5 + len) / 8 + 1) * 8);
+            assert_eq!(cipher.decrypt_record(&sealed).unwrap(), (0x17, value));
+        }
+    }
+
+    #[test]
+    fn record_invalid_length() {
+        let cipher = cipher();
+        let mut rng = CountingRng(0);
+
+        let short = cipher.encrypt_message(&mut rng, &[1, 0, 0, 0]);
+        assert_eq!(cipher.decrypt_record(&short), Err(Rc5Error::InvalidRecord));
+
+        for record in [
+            &[1, 4, 0, 0, 0, 0xAA][..],
+            &[1, 0, 0, 0, 0, 0xAA],
+            &[1, 1, 0, 0, 0],
+        ]
+        .iter()
+        {
+            let sealed = cipher.encrypt_message(&mut rng, record);
+            assert_eq!(cipher.decrypt_record(&sealed), Err(Rc5Error::InvalidRecord));
+        }
+
+        assert_eq!(
+            cipher.decrypt_record(&[0; 7]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+}
@@ -0,0 +1,321 @@
+/*
+ * Runtime selection of the block cipher mode, either via `Mode` or as a `BlockMode` trait object
+ */
+
+use alloc::vec::Vec;
+
+use crate::{Iv, Padding, Rc5, Rc5Error};
+
+/**
+ * Block cipher mode, e.g. as chosen by a configuration value
+ *
+ * ECB and CBC apply PKCS#7 padding, the remaining modes produce output as long as the input. The
+ * `std` feature adds `encrypt_to_writer` and `decrypt_from_reader` on top.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /** Electronic codebook, ignores the IV */
+    Ecb,
+    /** Cipher block chaining */
+    Cbc,
+    /** Counter mode, using the IV as nonce */
+    Ctr,
+    /** Cipher feedback with 64-bit feedback */
+    Cfb,
+    /** Output feedback */
+    Ofb,
+}
+
+impl Rc5 {
+    /**
+     * Encrypt data in the given mode, failing only if the CTR counter is exhausted
+     */
+    pub fn encrypt_with_mode(&self, mode: Mode, iv: Iv, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        // PKCS#7 padding accepts data of any length
+        match mode {
+            Mode::Ecb => self.encrypt_ecb(data, Padding::Pkcs7),
+            Mode::Cbc => self.encrypt_cbc(iv, data, Padding::Pkcs7),
+            Mode::Ctr => self.apply_ctr(iv.into_bytes(), data),
+            Mode::Cfb => Ok(self.encrypt_cfb64(iv, data)),
+            Mode::Ofb => Ok(self.apply_ofb(iv, data)),
+        }
+    }
+
+    /**
+     * Decrypt data in the given mode, failing like `decrypt_ecb`, `decrypt_cbc` or `apply_ctr`
+     */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    pub fn decrypt_with_mode(&self, mode: Mode, iv: Iv, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        match mode {
+            Mode::Ecb => self.decrypt_ecb(data, Padding::Pkcs7),
+            Mode::Cbc => self.decrypt_cbc(iv, data, Padding::Pkcs7),
+            Mode::Ctr => self.apply_ctr(iv.into_bytes(), data),
+            Mode::Cfb => Ok(self.decrypt_cfb64(iv, data)),
+            Mode::Ofb => Ok(self.apply_ofb(iv, data)),
+        }
+    }
+}
+
+/**
+ * Block cipher mode bound to a cipher and its IV or nonce
+ *
+ * The trait is object safe, so a mode chosen at runtime can be stored as `Box<dyn BlockMode>`.
+ */
+pub trait BlockMode {
+    /** Encrypt data of arbitrary length, failing only if the CTR counter is exhausted */
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error>;
+
+    /** Decrypt data, failing if it is malformed for the mode */
+    #[must_use = "decryption fails for malformed ciphertext"]
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error>;
+}
+
+/**
+ * ECB mode with PKCS#7 padding
+ */
+pub struct Ecb<'a> {
+    cipher: &'a Rc5,
+}
+
+impl<'a> Ecb<'a> {
+    /**
+     * Create mode for `cipher`
+     */
+    pub fn new(cipher: &'a Rc5) -> Ecb<'a> {
+        Ecb { cipher }
+    }
+}
+
+impl<'a> BlockMode for Ecb<'a> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher
+            .encrypt_with_mode(Mode::Ecb, Iv::new([0; 8]), data)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher
+            .decrypt_with_mode(Mode::Ecb, Iv::new([0; 8]), data)
+    }
+}
+
+/**
+ * CBC mode with PKCS#7 padding
+ */
+pub struct Cbc<'a> {
+    cipher: &'a Rc5,
+    iv: Iv,
+}
+
+impl<'a> Cbc<'a> {
+    /**
+     * Create mode for `cipher` and `iv`
+     */
+    pub fn new(cipher: &'a Rc5, iv: Iv) -> Cbc<'a> {
+        Cbc { cipher, iv }
+    }
+}
+
+impl<'a> BlockMode for Cbc<'a> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher.encrypt_with_mode(Mode::Cbc, self.iv, data)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher.decrypt_with_mode(Mode::Cbc, self.iv, data)
+    }
+}
+
+/**
+ * CTR mode, which only fails if the counter is exhausted
+ */
+pub struct Ctr<'a> {
+    cipher: &'a Rc5,
+    nonce: [u8; 8],
+}
+
+impl<'a> Ctr<'a> {
+    /**
+     * Create mode for `cipher` and `nonce`
+     */
+    pub fn new(cipher: &'a Rc5, nonce: [u8; 8]) -> Ctr<'a> {
+        Ctr { cipher, nonce }
+    }
+}
+
+impl<'a> BlockMode for Ctr<'a> {
+    fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher
+            .encrypt_with_mode(Mode::Ctr, Iv::new(self.nonce), data)
+    }
+
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        self.cipher
+            .decrypt_with_mode(Mode::Ctr, Iv::new(self.nonce), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::boxed::Box;
+    use alloc::vec;
+
+    use crate::CtsVariant;
+
+    const MODES: [Mode; 5] = [Mode::Ecb, Mode::Cbc, Mode::Ctr, Mode::Cfb, Mode::Ofb];
+
+    #[test]
+    fn block_mode_trait_objects() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..21).collect();
+
+        let modes: Vec<(Box<dyn BlockMode>, Vec<u8>)> = vec![
+            (
+                Box::new(Ecb::new(&cipher)),
+                cipher.encrypt_ecb(&plaintext, Padding::Pkcs7).unwrap(),
+            ),
+            (
+                Box::new(Cbc::new(&cipher, iv)),
+                cipher.encrypt_cbc(iv, &plaintext, Padding::Pkcs7).unwrap(),
+            ),
+            (
+                Box::new(Ctr::new(&cipher, iv.into_bytes())),
+                cipher.apply_ctr(iv.into_bytes(), &plaintext).unwrap(),
+            ),
+        ];
+
+        for (mode, expected) in modes.iter() {
+            let ciphertext = mode.encrypt(&plaintext).unwrap();
+            assert_eq!(ciphertext, *expected);
+            assert_eq!(mode.decrypt(&ciphertext).unwrap(), plaintext);
+        }
+
+        assert_eq!(
+            modes[1].0.decrypt(&[0; 12]),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn empty_and_single_byte_inputs() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::new(&key).unwrap();
+        let iv = Iv::new([0xA5; 8]);
+
+        for mode in MODES.iter() {
+            let padded = matches!(mode, Mode::Ecb | Mode::Cbc);
+
+            for plaintext in [&[][..], &[0x42]].iter() {
+                let ciphertext = cipher.encrypt_with_mode(*mode, iv, plaintext).unwrap();
+                let len = if padded { 8 } else { plaintext.len() };
+                assert_eq!(ciphertext.len(), len, "{:?}", mode);
+                assert_eq!(
+                    cipher.decrypt_with_mode(*mode, iv, &ciphertext).unwrap(),
+                    *plaintext,
+                    "{:?}",
+                    mode
+                );
+            }
+
+            let empty = cipher.decrypt_with_mode(*mode, iv, &[]);
+            assert_eq!(
+                empty,
+                if padded {
+                    Err(Rc5Error::EmptyInput)
+                } else {
+                    Ok(Vec::new())
+                },
+                "{:?}",
+                mode
+            );
+
+            if padded {
+                assert_eq!(
+                    cipher.decrypt_with_mode(*mode, iv, &[0x42]),
+                    Err(Rc5Error::InvalidBlockLength),
+                    "{:?}",
+                    mode
+                );
+            }
+        }
+
+        assert_eq!(cipher.encrypt_cfb8(iv, &[]), Vec::<u8>::new());
+        assert_eq!(
+            cipher.decrypt_cfb8(iv, &cipher.encrypt_cfb8(iv, &[0x42])),
+            [0x42]
+        );
+    }
+
+    #[test]
+    fn round_trip_matrix() {
+        let keys: [&[u8]; 3] = [&[0x01, 0x23, 0x45, 0x67, 0x89], &[0xFF; 16], &[0x5A; 32]];
+        let paddings = [
+            Padding::Pkcs7,
+            Padding::AnsiX923,
+            Padding::Iso7816,
+            Padding::ZeroPad,
+            Padding::NoPadding,
+        ];
+        let iv = Iv::new([0xA5; 8]);
+
+        for key in keys.iter() {
+            let cipher = Rc5::new(key).unwrap();
+
+            for len in [0, 1, 7, 8, 9, 16].iter() {
+                // Non-zero bytes, so that zero padding is unambiguous
+                let data: Vec<u8> = (1..=*len as u8).collect();
+                let aligned = len % 8 == 0;
+
+                for padding in paddings.iter() {
+                    let ecb = cipher.encrypt_ecb(&data, *padding);
+                    let cbc = cipher.encrypt_cbc(iv, &data, *padding);
+                    if *padding == Padding::NoPadding && !aligned {
+                        assert_eq!(ecb, Err(Rc5Error::InvalidBlockLength));
+                        assert_eq!(cbc, Err(Rc5Error::InvalidBlockLength));
+                        continue;
+                    }
+
+                    let (ecb, cbc) = (ecb.unwrap(), cbc.unwrap());
+                    let context = (len, padding);
+                    assert_eq!(
+                        cipher.decrypt_ecb(&ecb, *padding).unwrap(),
+                        data,
+                        "ECB {:?}",
+                        context
+                    );
+                    assert_eq!(
+                        cipher.decrypt_cbc(iv, &cbc, *padding).unwrap(),
+                        data,
+                        "CBC {:?}",
+                        context
+                    );
+                }
+
+                for mode in [Mode::Ctr, Mode::Cfb, Mode::Ofb].iter() {
+                    let ciphertext = cipher.encrypt_with_mode(*mode, iv, &data).unwrap();
+                    assert_eq!(
+                        cipher.decrypt_with_mode(*mode, iv, &ciphertext).unwrap(),
+                        data,
+                        "{:?} {}",
+                        mode,
+                        len
+                    );
+                }
+
+                let cfb8 = cipher.encrypt_cfb8(iv, &data);
+                assert_eq!(cipher.decrypt_cfb8(iv, &cfb8), data);
+
+                if *len >= 8 {
+                    for variant in [CtsVariant::Cs1, CtsVariant::Cs2, CtsVariant::Cs3].iter() {
+                        let cts = cipher.encrypt_cbc_cts(iv, &data, *variant).unwrap();
+                        assert_eq!(cts.len(), *len);
+                        assert_eq!(cipher.decrypt_cbc_cts(iv, &cts, *variant).unwrap(), data);
+                    }
+                }
+            }
+        }
+    }
+}
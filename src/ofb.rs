@@ -0,0 +1,156 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{xor_in_place, Iv, Rc5, BLOCK_BYTES};
+
+/**
+ * Output feedback (OFB) mode
+ *
+ * The keystream is produced by repeatedly encrypting the IV, i.e. it does not depend on the data
+ * and can be precomputed. A bit error in the ciphertext only affects the same bit of plaintext.
+ */
+impl Rc5 {
+    /**
+     * Encrypt or decrypt data of arbitrary length
+     *
+     * OFB mode XORs the data with a keystream, so the same operation serves for both directions.
+     * No padding is applied and the output is as long as the input.
+     */
+    pub fn apply_ofb(&self, iv: Iv, data: &[u8]) -> Vec<u8> {
+        let mut out = data.to_vec();
+        self.apply_ofb_in_place(iv, &mut out);
+        out
+    }
+
+    /**
+     * Encrypt or decrypt the buffer in place like `apply_ofb`
+     */
+    pub fn apply_ofb_in_place(&self, iv: Iv, buf: &mut [u8]) {
+        let mut block = iv.into_bytes();
+
+        for chunk in buf.chunks_mut(BLOCK_BYTES) {
+            block = self.encrypt_block(block);
+            xor_in_place(chunk, &block[..chunk.len()]);
+        }
+    }
+
+    /**
+     * Encrypt or decrypt owned or borrowed data like `apply_ofb`
+     *
+     * Owned data is processed in place and borrowed data copied unless it is empty, see
+     * `apply_ctr_cow`.
+     */
+    pub fn apply_ofb_cow<'a>(&self, iv: Iv, data: impl Into<Cow<'a, [u8]>>) -> Cow<'a, [u8]> {
+        let mut data = data.into();
+        if !data.is_empty() {
+            self.apply_ofb_in_place(iv, data.to_mut());
+        }
+        data
+    }
+
+    /**
+     * Lazily produce the OFB keystream for the given IV
+     *
+     * Only one encrypted block is buffered at a time, the next one is computed once it is
+     * exhausted. The iterator is unbounded, so it can be zipped against inputs of any length.
+     */
+    pub fn keystream_iter(&self, iv: Iv) -> impl Iterator<Item = u8> + '_ {
+        core::iter::repeat(())
+            .scan(iv.into_bytes(), move |block, ()| {
+                *block = self.encrypt_block(*block);
+                Some(*block)
+            })
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xor;
+
+    use alloc::vec;
+
+    fn cipher() -> Rc5 {
+        let key: Vec<u8> = (0..16).collect();
+        Rc5::new(&key).unwrap()
+    }
+
+    #[test]
+    fn apply_ofb_keystream() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        let first = cipher.encrypt_block(iv.into_bytes());
+        let second = cipher.encrypt_block(first);
+
+        let keystream = cipher.apply_ofb(iv, &[0; 12]);
+        assert_eq!(keystream[..8], first);
+        assert_eq!(keystream[8..], second[..4]);
+    }
+
+    #[test]
+    fn keystream_iter_matches_ofb() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        let keystream: Vec<u8> = cipher.keystream_iter(iv).take(20).collect();
+        assert_eq!(keystream, cipher.apply_ofb(iv, &[0; 20]));
+    }
+
+    #[test]
+    fn ofb_round_trip() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for len in 0..=24 {
+            let plaintext: Vec<u8> = (0..len as u8).collect();
+            let ciphertext = cipher.apply_ofb(iv, &plaintext);
+
+            assert_eq!(ciphertext.len(), len);
+            assert_eq!(cipher.apply_ofb(iv, &ciphertext), plaintext);
+        }
+    }
+
+    #[test]
+    fn ofb_partial_last_block() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+
+        for blocks in 0..3 {
+            let keystream = cipher.apply_ofb(iv, &vec![0; 8 * (blocks + 1)]);
+
+            for tail in 1..8 {
+                let len = 8 * blocks + tail;
+                let plaintext: Vec<u8> = (0..len).map(|i| 0x30 + i as u8).collect();
+                let ciphertext = cipher.apply_ofb(iv, &plaintext);
+
+                // Only the first `tail` bytes of the last keystream block are used
+                let expected = xor(&plaintext, &keystream[..len]);
+                assert_eq!(ciphertext, expected, "length {}", len);
+                assert_eq!(cipher.apply_ofb(iv, &ciphertext), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn ofb_in_place_and_cow() {
+        let cipher = cipher();
+        let iv = Iv::new([0xA5; 8]);
+        let plaintext: Vec<u8> = (0..13).collect();
+        let expected = cipher.apply_ofb(iv, &plaintext);
+
+        let mut buf = plaintext.clone();
+        cipher.apply_ofb_in_place(iv, &mut buf);
+        assert_eq!(buf, expected);
+
+        let result = cipher.apply_ofb_cow(iv, plaintext.clone());
+        assert!(matches!(result, Cow::Owned(_)));
+        assert_eq!(result, expected);
+        assert_eq!(cipher.apply_ofb_cow(iv, &plaintext[..]), expected);
+        assert!(matches!(
+            cipher.apply_ofb_cow(iv, &[][..]),
+            Cow::Borrowed(&[])
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+/*
+ * Block padding schemes for the block modes
+ */
+
+use alloc::vec::Vec;
+
+use crate::{Rc5Error, BLOCK_BYTES};
+
+/**
+ * Padding applied to the last block before encryption
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /** n bytes of value n */
+    Pkcs7,
+    /** n - 1 zero bytes followed by a byte of value n */
+    AnsiX923,
+    /** A 0x80 byte followed by zero bytes (ISO/IEC 7816-4) */
+    Iso7816,
+    /**
+     * Zero bytes up to the block boundary, none if the data is already aligned
+     *
+     * Trailing zero bytes of the plaintext are indistinguishable from padding and get stripped
+     * by `unpad`.
+     */
+    ZeroPad,
+    /** No padding, the data must consist of whole blocks */
+    NoPadding,
+}
+
+/**
+ * Pad data to a multiple of the block size
+ *
+ * All schemes except `ZeroPad` and `NoPadding` add a whole block if the data is already aligned.
+ */
+pub fn pad(data: &[u8], padding: Padding) -> Result<Vec<u8>, Rc5Error> {
+    let mut padded = Vec::with_capacity(data.len() + BLOCK_BYTES);
+    padded.extend_from_slice(data);
+    pad_in_place(&mut padded, padding)?;
+    Ok(padded)
+}
+
+/**
+ * Pad data like `pad`, appending to the existing buffer
+ */
+pub fn pad_in_place(buf: &mut Vec<u8>, padding: Padding) -> Result<(), Rc5Error> {
+    let len = buf.len();
+    let rem = len % BLOCK_BYTES;
+    let n = match padding {
+        Padding::Pkcs7 | Padding::AnsiX923 | Padding::Iso7816 => BLOCK_BYTES - rem,
+        Padding::ZeroPad => (BLOCK_BYTES - rem) % BLOCK_BYTES,
+        Padding::NoPadding if rem == 0 => 0,
+        Padding::NoPadding => return Err(Rc5Error::InvalidBlockLength),
+    };
+
+    match padding {
+        Padding::Pkcs7 => buf.resize(len + n, n as u8),
+        Padding::AnsiX923 => {
+            buf.resize(len + n - 1, 0);
+            buf.push(n as u8);
+        }
+        Padding::Iso7816 => {
+            buf.push(0x80);
+            buf.resize(len + n, 0);
+        }
+        Padding::ZeroPad | Padding::NoPadding => buf.resize(len + n, 0),
+    }
+
+    Ok(())
+}
+
+/**
+ * Strip padding, validating its contents for all schemes except `ZeroPad` and `NoPadding`
+ *
+ * Fails with `EmptyInput` for empty data if the scheme always adds padding, and with
+ * `InvalidPadding` if the padding is malformed.
+ */
+#[must_use = "unpadding fails for malformed padding"]
+pub fn unpad(data: &[u8], padding: Padding) -> Result<&[u8], Rc5Error> {
+    let always_padded = !matches!(padding, Padding::ZeroPad | Padding::NoPadding);
+    if always_padded && data.is_empty() {
+        return Err(Rc5Error::EmptyInput);
+    }
+
+    match padding {
+        Padding::Pkcs7 => pkcs7_unpad(data),
+        Padding::AnsiX923 => {
+            let n = padding_len(data)?;
+            check_zeros(data, n)
+        }
+        Padding::Iso7816 => {
+            let tail = &data[data.len().saturating_sub(BLOCK_BYTES)..];
+            let n = tail
+                .iter()
+                .rev()
+                .position(|&b| b != 0)
+                .map(|zeros| zeros + 1)
+                .ok_or(Rc5Error::InvalidPadding)?;
+
+            if data[data.len() - n] != 0x80 {
+                return Err(Rc5Error::InvalidPadding);
+            }
+
+            Ok(&data[..data.len() - n])
+        }
+        Padding::ZeroPad => {
+            let tail = &data[data.len().saturating_sub(BLOCK_BYTES)..];
+            let zeros = tail.iter().rev().take_while(|&&b| b == 0).count();
+            Ok(&data[..data.len() - zeros])
+        }
+        Padding::NoPadding => Ok(data),
+    }
+}
+
+/**
+ * Strip and validate PKCS#7 padding in constant time
+ *
+ * A decryption endpoint reporting invalid padding becomes a padding oracle if the time taken
+ * depends on where the padding check fails. Therefore all bytes of the final block are inspected,
+ * whatever the padding length, and every check is folded into a single mask using arithmetic
+ * only. The only branch is taken on the combined result. The data length is not secret, so
+ * rejecting data shorter than a block early is fine.
+ */
+fn pkcs7_unpad(data: &[u8]) -> Result<&[u8], Rc5Error> {
+    if data.len() < BLOCK_BYTES {
+        return Err(Rc5Error::InvalidPadding);
+    }
+
+    let block = &data[data.len() - BLOCK_BYTES..];
+    let n = block[BLOCK_BYTES - 1];
+
+    // Zero and lengths exceeding a block are invalid
+    let mut invalid = ct_lt(n, 1) | ct_lt(BLOCK_BYTES as u8, n);
+
+    for (i, &b) in block.iter().enumerate() {
+        // Byte i is part of the padding if its distance from the end does not exceed n
+        let in_padding = ct_lt((BLOCK_BYTES - i - 1) as u8, n);
+        invalid |= in_padding & (b ^ n);
+    }
+
+    if invalid != 0 {
+        return Err(Rc5Error::InvalidPadding);
+    }
+
+    Ok(&data[..data.len() - n as usize])
+}
+
+/**
+ * Return 0xFF if `a < b`, otherwise 0, without branching
+ */
+fn ct_lt(a: u8, b: u8) -> u8 {
+    // The subtraction borrows from the high byte exactly if a < b
+    ((a as u16).wrapping_sub(b as u16) >> 8) as u8
+}
+
+/**
+ * Read the padding length from the last byte, checking that it lies within the last block
+ */
+fn padding_len(data: &[u8]) -> Result<usize, Rc5Error> {
+    let n = *data.last().ok_or(Rc5Error::InvalidPadding)? as usize;
+
+    if n == 0 || n > BLOCK_BYTES || n > data.len() {
+        return Err(Rc5Error::InvalidPadding);
+    }
+
+    Ok(n)
+}
+
+/**
+ * Check that the n - 1 bytes preceding the length byte are zero
+ */
+fn check_zeros(data: &[u8], n: usize) -> Result<&[u8], Rc5Error> {
+    let (unpadded, tail) = data.split_at(data.len() - n);
+    if tail[..n - 1].iter().any(|&b| b != 0) {
+        return Err(Rc5Error::InvalidPadding);
+    }
+
+    Ok(unpadded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Padding; 5] = [
+        Padding::Pkcs7,
+        Padding::AnsiX923,
+        Padding::Iso7816,
+        Padding::ZeroPad,
+        Padding::NoPadding,
+    ];
+
+    #[test]
+    fn pad_schemes() {
+        let data = [0xAA; 5];
+
+        assert_eq!(pad(&data, Padding::Pkcs7).unwrap()[5..], [3, 3, 3]);
+        assert_eq!(pad(&data, Padding::AnsiX923).unwrap()[5..], [0, 0, 3]);
+        assert_eq!(pad(&data, Padding::Iso7816).unwrap()[5..], [0x80, 0, 0]);
+        assert_eq!(pad(&data, Padding::ZeroPad).unwrap()[5..], [0, 0, 0]);
+        assert_eq!(
+            pad(&data, Padding::NoPadding),
+            Err(Rc5Error::InvalidBlockLength)
+        );
+    }
+
+    #[test]
+    fn pad_aligned() {
+        let data = [0xAA; 8];
+
+        assert_eq!(pad(&data, Padding::Pkcs7).unwrap()[8..], [8; 8]);
+        assert_eq!(
+            pad(&data, Padding::AnsiX923).unwrap()[8..],
+            [0, 0, 0, 0, 0, 0, 0, 8]
+        );
+        assert_eq!(
+            pad(&data, Padding::Iso7816).unwrap()[8..],
+            [0x80, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(pad(&data, Padding::ZeroPad).unwrap(), data);
+        assert_eq!(pad(&data, Padding::NoPadding).unwrap(), data);
+    }
+
+    #[test]
+    fn pad_round_trip() {
+        for padding in ALL.iter() {
+            for len in 0..=24 {
+                // Non-zero last byte, so that zero padding is unambiguous
+                let data: Vec<u8> = (1..=len as u8).collect();
+
+                let padded = match pad(&data, *padding) {
+                    Ok(padded) => padded,
+                    Err(_) => {
+                        assert_eq!(*padding, Padding::NoPadding);
+                        continue;
+                    }
+                };
+
+                assert_eq!(padded.len() % BLOCK_BYTES, 0);
+                assert_eq!(unpad(&padded, *padding).unwrap(), &data[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn ct_lt_mask() {
+        for a in 0..=255u8 {
+            for b in 0..=255u8 {
+                assert_eq!(ct_lt(a, b), if a < b { 0xFF } else { 0 });
+            }
+        }
+    }
+
+    #[test]
+    fn pkcs7_unpad_lengths() {
+        for n in 1..=8u8 {
+            let mut block = [0xAA; 8];
+            block[8 - n as usize..].iter_mut().for_each(|b| *b = n);
+            assert_eq!(unpad(&block, Padding::Pkcs7).unwrap().len(), 8 - n as usize);
+        }
+
+        assert_eq!(
+            unpad(&[1; 7], Padding::Pkcs7),
+            Err(Rc5Error::InvalidPadding)
+        );
+    }
+
+    #[test]
+    fn unpad_invalid() {
+        let invalid = [
+            (Padding::Pkcs7, [0; 8]),
+            (Padding::Pkcs7, [9; 8]),
+            (Padding::Pkcs7, [1, 2, 3, 4, 5, 6, 2, 3]),
+            (Padding::Pkcs7, [8, 8, 8, 8, 8, 8, 8, 0xF8]),
+            (Padding::Pkcs7, [7, 8, 8, 8, 8, 8, 8, 8]),
+            (Padding::AnsiX923, [0; 8]),
+            (Padding::AnsiX923, [0, 0, 0, 0, 0, 1, 0, 3]),
+            (Padding::Iso7816, [0; 8]),
+            (Padding::Iso7816, [1, 2, 3, 4, 5, 6, 7, 0]),
+        ];
+
+        for (padding, block) in invalid.iter() {
+            assert_eq!(unpad(block, *padding), Err(Rc5Error::InvalidPadding));
+        }
+
+        for padding in [Padding::Pkcs7, Padding::AnsiX923, Padding::Iso7816].iter() {
+            assert_eq!(unpad(&[], *padding), Err(Rc5Error::EmptyInput));
+        }
+    }
+}
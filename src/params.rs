@@ -0,0 +1,70 @@
+/*
+ * Introspection of the parameters of a constructed cipher
+ */
+
+use crate::{Rc5Cipher, Word};
+
+/**
+ * Parameters of a cipher, as returned by `Rc5Cipher::params`
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CipherParams {
+    /** Word size w in bits */
+    pub word_bits: usize,
+    /** Number of rounds r */
+    pub rounds: usize,
+    /** Key length b in bytes, `None` if the cipher was created from a key table */
+    pub key_len: Option<usize>,
+    /** Block size in bytes, i.e. two words */
+    pub block_bytes: usize,
+    /** Number of words in the expanded key table, 2r + 2 */
+    pub key_table_words: usize,
+}
+
+impl<W: Word> Rc5Cipher<W> {
+    /**
+     * Parameters the cipher was constructed with
+     */
+    pub fn params(&self) -> CipherParams {
+        CipherParams {
+            word_bits: W::BITS,
+            rounds: self.rounds(),
+            key_len: self.key_len(),
+            block_bytes: 2 * W::BYTES,
+            key_table_words: self.expanded_key().len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rc5;
+
+    #[test]
+    fn params() {
+        let cipher = Rc5::new(&[0xAB; 16]).unwrap();
+        assert_eq!(
+            cipher.params(),
+            CipherParams {
+                word_bits: 32,
+                rounds: 12,
+                key_len: Some(16),
+                block_bytes: 8,
+                key_table_words: 26,
+            }
+        );
+
+        let params = Rc5Cipher::<u64>::with_rounds(&[0xAB; 10], 24)
+            .unwrap()
+            .params();
+        assert_eq!(
+            (params.word_bits, params.rounds, params.key_len),
+            (64, 24, Some(10))
+        );
+        assert_eq!((params.block_bytes, params.key_table_words), (16, 50));
+
+        let restored = Rc5::from_key_table(cipher.expanded_key().to_vec()).unwrap();
+        assert_eq!(restored.params().key_len, None);
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Key derivation from passphrases with PBKDF2-HMAC-SHA256
+ */
+
+use sha2::Sha256;
+
+use crate::Rc5;
+
+/** Number of PBKDF2 iterations used by `from_passphrase`, as recommended by OWASP */
+pub static DEFAULT_PBKDF2_ITERATIONS: u32 = 600_000;
+
+/** Length of the derived key in bytes */
+const KEY_BYTES: usize = 16;
+
+impl Rc5 {
+    /**
+     * Create cipher with a 16-byte key derived from a passphrase, using
+     * `DEFAULT_PBKDF2_ITERATIONS`
+     *
+     * This is a convenience only. The key derivation slows down guessing weak passphrases, but
+     * does not strengthen the cipher itself, whose security limits still apply.
+     */
+    pub fn from_passphrase(passphrase: &str, salt: &[u8]) -> Rc5 {
+        Rc5::from_passphrase_with_iterations(passphrase, salt, DEFAULT_PBKDF2_ITERATIONS)
+    }
+
+    /**
+     * Create cipher with a key derived from a passphrase using the given number of iterations
+     */
+    pub fn from_passphrase_with_iterations(passphrase: &str, salt: &[u8], iterations: u32) -> Rc5 {
+        let mut key = [0; KEY_BYTES];
+        pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+
+        // A 16-byte key is always valid
+        let cipher = Rc5::new(&key).unwrap();
+
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(&mut key);
+
+        cipher
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pbkdf2_key() {
+        // Commonly used PBKDF2-HMAC-SHA256 vector for "password" with salt "salt" and one iteration
+        let key = [
+            0x12, 0x0f, 0xb6, 0xcf, 0xfc, 0xf8, 0xb3, 0x2c, 0x43, 0xe7, 0x22, 0x52, 0x56, 0xc4,
+            0xf8, 0x37,
+        ];
+
+        let cipher = Rc5::from_passphrase_with_iterations("password", b"salt", 1);
+        assert_eq!(
+            cipher.expanded_key(),
+            Rc5::new(&key).unwrap().expanded_key()
+        );
+    }
+
+    #[test]
+    fn salt_matters() {
+        let a = Rc5::from_passphrase_with_iterations("password", b"salt", 2);
+        let b = Rc5::from_passphrase_with_iterations("password", b"pepper", 2);
+        assert_ne!(a.expanded_key(), b.expanded_key());
+    }
+}
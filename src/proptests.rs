@@ -0,0 +1,51 @@
+/*
+ * Property-based round-trip tests over random keys, blocks and messages
+ */
+
+use proptest::prelude::*;
+
+use crate::{Iv, Padding, Rc5};
+
+/** Kept low so that the test suite stays fast */
+const CASES: u32 = 64;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn block_round_trip(key in any::<[u8; 16]>(), block in any::<[u8; 8]>()) {
+        let cipher = Rc5::new(&key).unwrap();
+        prop_assert_eq!(cipher.decrypt_block(cipher.encrypt_block(block)), block);
+    }
+
+    #[test]
+    fn ecb_round_trip(key in any::<[u8; 16]>(), data in prop::collection::vec(any::<u8>(), 0..100)) {
+        let cipher = Rc5::new(&key).unwrap();
+        let ciphertext = cipher.encrypt_ecb(&data, Padding::Pkcs7).unwrap();
+        prop_assert_eq!(cipher.decrypt_ecb(&ciphertext, Padding::Pkcs7).unwrap(), data);
+    }
+
+    #[test]
+    fn cbc_round_trip(
+        key in any::<[u8; 16]>(),
+        iv in any::<[u8; 8]>(),
+        data in prop::collection::vec(any::<u8>(), 0..100),
+    ) {
+        let cipher = Rc5::new(&key).unwrap();
+        let iv = Iv::new(iv);
+        let ciphertext = cipher.encrypt_cbc(iv, &data, Padding::Pkcs7).unwrap();
+        prop_assert_eq!(cipher.decrypt_cbc(iv, &ciphertext, Padding::Pkcs7).unwrap(), data);
+    }
+
+    #[test]
+    fn ctr_round_trip(
+        key in any::<[u8; 16]>(),
+        nonce in any::<[u8; 8]>(),
+        data in prop::collection::vec(any::<u8>(), 0..100),
+    ) {
+        let cipher = Rc5::new(&key).unwrap();
+        let ciphertext = cipher.apply_ctr(nonce, &data).unwrap();
+        prop_assert_eq!(ciphertext.len(), data.len());
+        prop_assert_eq!(cipher.apply_ctr(nonce, &ciphertext).unwrap(), data);
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ * RC6 based on the AES submission by Rivest, Robshaw, Sidney and Yin (1998) [1]
+ *
+ * RC6 extends RC5 to four registers and adds integer multiplication to the round function. The
+ * key schedule is the same as RC5's apart from the size of the key table.
+ *
+ * [1] https://people.csail.mit.edu/rivest/pubs/RRSY98.pdf
+ */
+
+use alloc::vec::Vec;
+
+use crate::{check_rounds, key_table_with_len, ByteOrder, Rc5Error, Word};
+
+/** Number of rounds used by `Rc6::new` */
+pub static DEFAULT_ROUNDS: usize = 20;
+
+/**
+ * Number of words in the expanded key table
+ */
+fn key_table_words(rounds: usize) -> usize {
+    2 * rounds + 4
+}
+
+/**
+ * RC6 with 32-bit words, i.e. 16-byte blocks
+ */
+pub struct Rc6 {
+    rounds: usize,
+    key_table: Vec<u32>,
+}
+
+/**
+ * Wipe the key table when the cipher goes out of scope
+ */
+#[cfg(feature = "zeroize")]
+impl Drop for Rc6 {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.key_table);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Rc6 {}
+
+impl Rc6 {
+    /**
+     * Create cipher for the given secret key using `DEFAULT_ROUNDS`
+     */
+    pub fn new(key: &[u8]) -> Result<Rc6, Rc5Error> {
+        Rc6::with_rounds(key, DEFAULT_ROUNDS)
+    }
+
+    /**
+     * Create cipher for the given secret key and number of rounds
+     */
+    pub fn with_rounds(key: &[u8], rounds: usize) -> Result<Rc6, Rc5Error> {
+        check_rounds(rounds)?;
+
+        Ok(Rc6 {
+            rounds,
+            key_table: key_table(key, rounds)?,
+        })
+    }
+
+    /**
+     * Encrypt a single 16-byte block
+     */
+    pub fn encrypt_block(&self, block: [u8; 16]) -> [u8; 16] {
+        unpack_block(encode(&self.key_table, self.rounds, pack_block(block)))
+    }
+
+    /**
+     * Decrypt a single 16-byte block
+     */
+    pub fn decrypt_block(&self, block: [u8; 16]) -> [u8; 16] {
+        unpack_block(decode(&self.key_table, self.rounds, pack_block(block)))
+    }
+}
+
+/**
+ * Convert block to its four little-endian words
+ */
+fn pack_block(block: [u8; 16]) -> [u32; 4] {
+    let mut words = [0; 4];
+    for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = Word::from_le_bytes(chunk);
+    }
+    words
+}
+
+/**
+ * Convert four words back to a block
+ */
+fn unpack_block(words: [u32; 4]) -> [u8; 16] {
+    let mut block = [0; 16];
+    for (chunk, word) in block.chunks_exact_mut(4).zip(words.iter()) {
+        word.write_le_bytes(chunk);
+    }
+    block
+}
+
+/**
+ * Expand key table
+ */
+fn key_table<W: Word>(key: &[u8], rounds: usize) -> Result<Vec<W>, Rc5Error> {
+    key_table_with_len(key, key_table_words(rounds), ByteOrder::LittleEndian)
+}
+
+/**
+ * Quadratic function f(x) = x * (2x + 1) rotated left by lg(w) bits
+ */
+fn f<W: Word>(x: W) -> W {
+    let lg_w = W::BITS.trailing_zeros();
+    x.wrapping_mul(x.wrapping_add(x).wrapping_add(W::from(1)))
+        .rotate_left(lg_w)
+}
+
+/**
+ * Return ciphertext for a given key table and plaintext
+ */
+fn encode<W: Word>(key_table: &[W], rounds: usize, plaintext: [W; 4]) -> [W; 4] {
+    assert_eq!(key_table.len(), key_table_words(rounds));
+
+    let [a, mut b, c, mut d] = plaintext;
+    b = b.wrapping_add(key_table[0]);
+    d = d.wrapping_add(key_table[1]);
+
+    let (mut a, b, mut c, d) = (1..(rounds + 1)).fold((a, b, c, d), |acc, i| {
+        let (mut a, b, mut c, d) = acc;
+        let t = f(b);
+        let u = f(d);
+        a = (a ^ t)
+            .rotate_left(u.as_shift())
+            .wrapping_add(key_table[2 * i]);
+        c = (c ^ u)
+            .rotate_left(t.as_shift())
+            .wrapping_add(key_table[2 * i + 1]);
+        (b, c, d, a)
+    });
+
+    a = a.wrapping_add(key_table[2 * rounds + 2]);
+    c = c.wrapping_add(key_table[2 * rounds + 3]);
+
+    [a, b, c, d]
+}
+
+/**
+ * Return plaintext for a given key table and ciphertext
+ */
+fn decode<W: Word>(key_table: &[W], rounds: usize, ciphertext: [W; 4]) -> [W; 4] {
+    assert_eq!(key_table.len(), key_table_words(rounds));
+
+    let [mut a, b, mut c, d] = ciphertext;
+    c = c.wrapping_sub(key_table[2 * rounds + 3]);
+    a = a.wrapping_sub(key_table[2 * rounds + 2]);
+
+    let (a, mut b, c, mut d) = (1..(rounds + 1)).rev().fold((a, b, c, d), |acc, i| {
+        let (b, mut c, d, mut a) = acc;
+        let u = f(d);
+        let t = f(b);
+        c = c
+            .wrapping_sub(key_table[2 * i + 1])
+            .rotate_right(t.as_shift())
+            ^ u;
+        a = a.wrapping_sub(key_table[2 * i]).rotate_right(u.as_shift()) ^ t;
+        (a, b, c, d)
+    });
+
+    d = d.wrapping_sub(key_table[1]);
+    b = b.wrapping_sub(key_table[0]);
+
+    [a, b, c, d]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * Test vectors for RC6-32/20/b from the AES submission
+     */
+    static VECTORS: &[(&[u8], [u8; 16], [u8; 16])] = &[
+        (
+            &[0; 16],
+            [0; 16],
+            [
+                0x8f, 0xc3, 0xa5, 0x36, 0x56, 0xb1, 0xf7, 0x78, 0xc1, 0x29, 0xdf, 0x4e, 0x98, 0x48,
+                0xa4, 0x1e,
+            ],
+        ),
+        (
+            &[
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x12, 0x23, 0x34, 0x45, 0x56,
+                0x67, 0x78,
+            ],
+            [
+                0x02, 0x13, 0x24, 0x35, 0x46, 0x57, 0x68, 0x79, 0x8a, 0x9b, 0xac, 0xbd, 0xce, 0xdf,
+                0xe0, 0xf1,
+            ],
+            [
+                0x52, 0x4e, 0x19, 0x2f, 0x47, 0x15, 0xc6, 0x23, 0x1f, 0x51, 0xf6, 0x36, 0x7e, 0xa4,
+                0x3f, 0x18,
+            ],
+        ),
+        (
+            &[0; 24],
+            [0; 16],
+            [
+                0x6c, 0xd6, 0x1b, 0xcb, 0x19, 0x0b, 0x30, 0x38, 0x4e, 0x8a, 0x3f, 0x16, 0x86, 0x90,
+                0xae, 0x82,
+            ],
+        ),
+        (
+            &[
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x12, 0x23, 0x34, 0x45, 0x56,
+                0x67, 0x78, 0x89, 0x9a, 0xab, 0xbc, 0xcd, 0xde, 0xef, 0xf0,
+            ],
+            [
+                0x02, 0x13, 0x24, 0x35, 0x46, 0x57, 0x68, 0x79, 0x8a, 0x9b, 0xac, 0xbd, 0xce, 0xdf,
+                0xe0, 0xf1,
+            ],
+            [
+                0x68, 0x83, 0x29, 0xd0, 0x19, 0xe5, 0x05, 0x04, 0x1e, 0x52, 0xe9, 0x2a, 0xf9, 0x52,
+                0x91, 0xd4,
+            ],
+        ),
+        (
+            &[0; 32],
+            [0; 16],
+            [
+                0x8f, 0x5f, 0xbd, 0x05, 0x10, 0xd1, 0x5f, 0xa8, 0x93, 0xfa, 0x3f, 0xda, 0x6e, 0x85,
+                0x7e, 0xc2,
+            ],
+        ),
+        (
+            &[
+                0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x12, 0x23, 0x34, 0x45, 0x56,
+                0x67, 0x78, 0x89, 0x9a, 0xab, 0xbc, 0xcd, 0xde, 0xef, 0xf0, 0x10, 0x32, 0x54, 0x76,
+                0x98, 0xba, 0xdc, 0xfe,
+            ],
+            [
+                0x02, 0x13, 0x24, 0x35, 0x46, 0x57, 0x68, 0x79, 0x8a, 0x9b, 0xac, 0xbd, 0xce, 0xdf,
+                0xe0, 0xf1,
+            ],
+            [
+                0xc8, 0x24, 0x18, 0x16, 0xf0, 0xd7, 0xe4, 0x89, 0x20, 0xad, 0x16, 0xa1, 0x67, 0x4e,
+                0x5d, 0x48,
+            ],
+        ),
+    ];
+
+    #[test]
+    fn encrypt_vectors() {
+        for (key, plaintext, ciphertext) in VECTORS.iter() {
+            let cipher = Rc6::new(key).unwrap();
+            assert_eq!(cipher.encrypt_block(*plaintext), *ciphertext);
+        }
+    }
+
+    #[test]
+    fn decrypt_vectors() {
+        for (key, plaintext, ciphertext) in VECTORS.iter() {
+            let cipher = Rc6::new(key).unwrap();
+            assert_eq!(cipher.decrypt_block(*ciphertext), *plaintext);
+        }
+    }
+
+    #[test]
+    fn rounds_out_of_range() {
+        assert!(Rc6::with_rounds(&[0; 16], 256).is_err());
+    }
+}
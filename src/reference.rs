@@ -0,0 +1,209 @@
+/*
+ * Comparison against a second, deliberately naive implementation of RC5
+ *
+ * The reference follows the pseudocode of the paper as literally as possible. All words are held
+ * in a `u64` and masked to w bits after every operation, so that it shares no code with `Word` or
+ * the key schedule of the crate.
+ */
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Rc5Cipher, Word};
+
+/** Blocks encrypted per word size */
+const BLOCKS: usize = 4096;
+
+/** Blocks encrypted with the same key */
+const BLOCKS_PER_KEY: usize = 64;
+
+struct Reference {
+    w: u32,
+    r: usize,
+    s: Vec<u64>,
+}
+
+impl Reference {
+    fn new(w: u32, r: usize, k: &[u8]) -> Reference {
+        let (p, q) = match w {
+            16 => (0xb7e1, 0x9e37),
+            32 => (0xb7e1_5163, 0x9e37_79b9),
+            64 => (0xb7e1_5162_8aed_2a6b, 0x9e37_79b9_7f4a_7c15),
+            _ => unreachable!(),
+        };
+
+        let u = w as usize / 8;
+        let b = k.len();
+        let c = if b == 0 { 1 } else { b.div_ceil(u) };
+        let t = 2 * (r + 1);
+
+        let mut l = vec![0u64; c];
+        for i in (0..b).rev() {
+            l[i / u] = (l[i / u] << 8) + k[i] as u64;
+        }
+
+        let mut s = vec![0u64; t];
+        s[0] = p;
+        for i in 1..t {
+            s[i] = add(w, s[i - 1], q);
+        }
+
+        let (mut a, mut bb, mut i, mut j) = (0, 0, 0, 0);
+        for _ in 0..3 * t.max(c) {
+            s[i] = rotl(w, add(w, s[i], add(w, a, bb)), 3);
+            a = s[i];
+            l[j] = rotl(w, add(w, l[j], add(w, a, bb)), add(w, a, bb));
+            bb = l[j];
+            i = (i + 1) % t;
+            j = (j + 1) % c;
+        }
+
+        Reference { w, r, s }
+    }
+
+    fn encrypt(&self, pt: &[u8]) -> Vec<u8> {
+        let w = self.w;
+        let (a, b) = split(w, pt);
+
+        let mut a = add(w, a, self.s[0]);
+        let mut b = add(w, b, self.s[1]);
+        for i in 1..=self.r {
+            a = add(w, rotl(w, a ^ b, b), self.s[2 * i]);
+            b = add(w, rotl(w, b ^ a, a), self.s[2 * i + 1]);
+        }
+
+        join(w, a, b)
+    }
+
+    fn decrypt(&self, ct: &[u8]) -> Vec<u8> {
+        let w = self.w;
+        let (mut a, mut b) = split(w, ct);
+
+        for i in (1..=self.r).rev() {
+            b = rotr(w, sub(w, b, self.s[2 * i + 1]), a) ^ a;
+            a = rotr(w, sub(w, a, self.s[2 * i]), b) ^ b;
+        }
+        b = sub(w, b, self.s[1]);
+        a = sub(w, a, self.s[0]);
+
+        join(w, a, b)
+    }
+}
+
+fn mask(w: u32) -> u64 {
+    if w == 64 {
+        u64::MAX
+    } else {
+        (1 << w) - 1
+    }
+}
+
+fn add(w: u32, x: u64, y: u64) -> u64 {
+    x.wrapping_add(y) & mask(w)
+}
+
+fn sub(w: u32, x: u64, y: u64) -> u64 {
+    x.wrapping_sub(y) & mask(w)
+}
+
+fn rotl(w: u32, x: u64, y: u64) -> u64 {
+    let y = (y % w as u64) as u32;
+    if y == 0 {
+        x
+    } else {
+        ((x << y) | (x >> (w - y))) & mask(w)
+    }
+}
+
+fn rotr(w: u32, x: u64, y: u64) -> u64 {
+    let y = (y % w as u64) as u32;
+    if y == 0 {
+        x
+    } else {
+        ((x >> y) | (x << (w - y))) & mask(w)
+    }
+}
+
+/** Read the two little-endian words of a block */
+fn split(w: u32, block: &[u8]) -> (u64, u64) {
+    let u = w as usize / 8;
+    let word = |bytes: &[u8]| bytes.iter().rev().fold(0, |v, &b| (v << 8) | b as u64);
+    (word(&block[..u]), word(&block[u..2 * u]))
+}
+
+fn join(w: u32, a: u64, b: u64) -> Vec<u8> {
+    let u = w as usize / 8;
+    (0..u)
+        .map(|i| (a >> (8 * i)) as u8)
+        .chain((0..u).map(|i| (b >> (8 * i)) as u8))
+        .collect()
+}
+
+/** Xorshift generator, so that the test is reproducible without further dependencies */
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+}
+
+fn compare<W: Word>(seed: u64) {
+    let mut rng = Xorshift(seed);
+
+    for _ in 0..BLOCKS / BLOCKS_PER_KEY {
+        let rounds = rng.next() as usize % 25;
+        let key_len = rng.next() as usize % 33;
+        let key = rng.bytes(key_len);
+
+        let cipher = Rc5Cipher::<W>::with_rounds(&key, rounds).unwrap();
+        let reference = Reference::new(W::BITS as u32, rounds, &key);
+        assert_eq!(
+            cipher.expanded_key().len(),
+            reference.s.len(),
+            "key {:02x?}",
+            key
+        );
+
+        for _ in 0..BLOCKS_PER_KEY {
+            let mut block = W::Block::default();
+            block.as_mut().copy_from_slice(&rng.bytes(2 * W::BYTES));
+
+            let ciphertext = cipher.encrypt_block(block);
+            let expected = reference.encrypt(block.as_ref());
+            assert_eq!(
+                ciphertext.as_ref(),
+                &expected[..],
+                "RC5-{}/{} key {:02x?} block {:02x?}",
+                W::BITS,
+                rounds,
+                key,
+                block
+            );
+            assert_eq!(reference.decrypt(&expected), block.as_ref());
+            assert_eq!(cipher.decrypt_block(ciphertext), block);
+        }
+    }
+}
+
+#[test]
+fn matches_reference_rc5_16() {
+    compare::<u16>(0x0123_4567_89ab_cdef);
+}
+
+#[test]
+fn matches_reference_rc5_32() {
+    compare::<u32>(0xfedc_ba98_7654_3210);
+}
+
+#[test]
+fn matches_reference_rc5_64() {
+    compare::<u64>(0x0f1e_2d3c_4b5a_6978);
+}
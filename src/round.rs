@@ -0,0 +1,52 @@
+/*
+ * Round function of the cipher
+ *
+ * RC5 rotates by amounts taken from the data itself, which is what gives the cipher its strength
+ * against differential and linear cryptanalysis. It is also a timing concern: on CPUs without a
+ * constant-time barrel shifter, such as some microcontrollers, a rotation takes longer the farther
+ * it rotates, so the running time leaks the rotation amounts and thereby the intermediate state.
+ * Handschuh and Heys (1998) [1] show how such timings recover the key table. Modern desktop
+ * and server CPUs rotate in constant time, but the compiler may still lower a rotation into
+ * shifts and branches on targets without a rotate instruction.
+ *
+ * With the `ct-study` feature, this module is public and its functions are never inlined, so
+ * that a single round can be benchmarked in isolation and located in the generated machine code.
+ * Without the feature, the behaviour of the cipher is the same, but the functions are inlined.
+ *
+ * [1] H. Handschuh, H. M. Heys, "A Timing Attack on RC5", Selected Areas in Cryptography 1998
+ */
+
+use crate::Word;
+
+/**
+ * Apply a round other than the first with its round keys S[2i] and S[2i + 1]
+ */
+#[cfg_attr(feature = "ct-study", inline(never))]
+pub fn encode_pair<W: Word>((a, b): (W, W), s0: W, s1: W) -> (W, W) {
+    let a = (a ^ b).rotate_left(b.as_shift()).wrapping_add(s0);
+    let b = (b ^ a).rotate_left(a.as_shift()).wrapping_add(s1);
+    (a, b)
+}
+
+/**
+ * Invert `encode_pair`
+ */
+#[cfg_attr(feature = "ct-study", inline(never))]
+pub fn decode_pair<W: Word>((a, b): (W, W), s0: W, s1: W) -> (W, W) {
+    let b = b.wrapping_sub(s1).rotate_right(a.as_shift()) ^ a;
+    let a = a.wrapping_sub(s0).rotate_right(b.as_shift()) ^ b;
+    (a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_pair_inverts_encode_pair() {
+        let state = (0x0123_4567u32, 0x89AB_CDEF);
+        for (s0, s1) in [(0, 0), (1, 31), (0xDEAD_BEEF, 0xFFFF_FFFF)].iter() {
+            assert_eq!(decode_pair(encode_pair(state, *s0, *s1), *s0, *s1), state);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * Serde support for storing a configured cipher
+ *
+ * Only the number of rounds and the expanded key table are serialized, so that the key schedule
+ * does not need to be run again. The secret key itself is never stored, but the key table is
+ * equally sensitive.
+ */
+
+use alloc::vec::Vec;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ByteOrder, Rc5Cipher, Word};
+
+#[derive(Serialize)]
+struct KeyTableRef<'a, W> {
+    rounds: usize,
+    key_table: &'a [W],
+    byte_order: ByteOrder,
+}
+
+#[derive(Deserialize)]
+struct KeyTable<W> {
+    rounds: usize,
+    key_table: Vec<W>,
+    #[serde(default)]
+    byte_order: ByteOrder,
+}
+
+impl<W: Word + Serialize> Serialize for Rc5Cipher<W> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        KeyTableRef {
+            rounds: self.rounds(),
+            key_table: self.expanded_key(),
+            byte_order: self.byte_order(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/**
+ * Deserialize the cipher, validating the key table length against the number of rounds
+ */
+impl<'de, W: Word + Deserialize<'de>> Deserialize<'de> for Rc5Cipher<W> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Rc5Cipher<W>, D::Error> {
+        let table = KeyTable::deserialize(deserializer)?;
+
+        Rc5Cipher::from_parts(table.key_table, table.rounds, table.byte_order)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ByteOrder, Rc5};
+
+    #[test]
+    fn json_round_trip() {
+        let key: Vec<u8> = (0..16).collect();
+        let cipher = Rc5::with_rounds(&key, 16).unwrap();
+
+        let json = serde_json::to_string(&cipher).unwrap();
+        let restored: Rc5 = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.rounds(), 16);
+        assert_eq!(restored.expanded_key(), cipher.expanded_key());
+    }
+
+    #[test]
+    fn json_byte_order() {
+        let cipher = Rc5::with_byte_order(&[0; 16], 12, ByteOrder::BigEndian).unwrap();
+
+        let json = serde_json::to_string(&cipher).unwrap();
+        let restored: Rc5 = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.byte_order(), ByteOrder::BigEndian);
+    }
+
+    #[test]
+    fn json_invalid_length() {
+        let json = r#"{"rounds":12,"key_table":[1,2,3]}"#;
+        let err = serde_json::from_str::<Rc5>(json).err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("key table has 3 words, expected 26"));
+
+        let json = r#"{"rounds":256,"key_table":[]}"#;
+        assert!(serde_json::from_str::<Rc5>(json).is_err());
+    }
+}
@@ -1,47 +1,234 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{ByteOrder, Word};
+
 pub trait ArithExt<T> {
-    /** Add operator with standard overflow semantics */
-    fn platform_add(self, val: T) -> T;
+    /** Addition modulo 2^w */
+    fn wrapping_add(self, val: T) -> T;
+
+    /** Subtraction modulo 2^w */
+    fn wrapping_sub(self, val: T) -> T;
+
+    /** Multiplication modulo 2^w */
+    fn wrapping_mul(self, val: T) -> T;
+}
+
+/*
+ * With the `verify-arithmetic` feature, debug builds check every result against a reference
+ * computed in 128-bit arithmetic and reduced modulo 2^w. Words are at most 64 bits wide, so the
+ * reference cannot overflow itself.
+ */
+macro_rules! impl_arith_ext {
+    ($t:ty) => {
+        impl ArithExt<$t> for $t {
+            fn wrapping_add(self, val: $t) -> $t {
+                let res = <$t>::wrapping_add(self, val);
+                #[cfg(feature = "verify-arithmetic")]
+                debug_assert_eq!(
+                    res as u128,
+                    (self as u128 + val as u128) % modulus::<$t>(),
+                    "{} + {}",
+                    self,
+                    val
+                );
+                res
+            }
+
+            fn wrapping_sub(self, val: $t) -> $t {
+                let res = <$t>::wrapping_sub(self, val);
+                #[cfg(feature = "verify-arithmetic")]
+                debug_assert_eq!(
+                    res as u128,
+                    (self as u128 + modulus::<$t>() - val as u128) % modulus::<$t>(),
+                    "{} - {}",
+                    self,
+                    val
+                );
+                res
+            }
+
+            fn wrapping_mul(self, val: $t) -> $t {
+                let res = <$t>::wrapping_mul(self, val);
+                #[cfg(feature = "verify-arithmetic")]
+                debug_assert_eq!(
+                    res as u128,
+                    (self as u128 * val as u128) % modulus::<$t>(),
+                    "{} * {}",
+                    self,
+                    val
+                );
+                res
+            }
+        }
+    };
+}
 
-    /** Subtraction operator with standard overflow semantics */
-    fn platform_sub(self, val: T) -> T;
+/**
+ * Modulus 2^w of the word arithmetic
+ */
+#[cfg(feature = "verify-arithmetic")]
+fn modulus<T>() -> u128 {
+    1 << (8 * core::mem::size_of::<T>())
 }
 
-impl ArithExt<u32> for u32 {
-    fn platform_add(self, val: u32) -> u32 {
-        let (res, _) = self.overflowing_add(val);
-        res
+impl_arith_ext!(u16);
+impl_arith_ext!(u32);
+impl_arith_ext!(u64);
+
+/**
+ * Compare two byte strings in constant time, e.g. authentication tags
+ *
+ * The differences of all byte pairs are OR-folded, so the time taken does not depend on the
+ * position of the first mismatch. Only the lengths, which are not secret, are compared upfront.
+ */
+#[must_use = "comparing in constant time is pointless if the result is ignored"]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
     }
 
-    fn platform_sub(self, val: u32) -> u32 {
-        let (res, _) = self.overflowing_sub(val);
-        res
+    let diff = a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+/**
+ * XOR `src` into `dst`, e.g. keystream into data
+ *
+ * Panics if the slices differ in length. Callers with a partial final block XOR only the matching
+ * prefix of the keystream.
+ */
+pub fn xor_in_place(dst: &mut [u8], src: &[u8]) {
+    assert_eq!(dst.len(), src.len(), "XOR of slices of different lengths");
+    for (d, s) in dst.iter_mut().zip(src.iter()) {
+        *d ^= s;
     }
 }
 
 /**
- * Taken from https://stackoverflow.com/a/56677696/13300239
+ * XOR two slices of equal length into a new vector, see `xor_in_place`
  */
+pub fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    let mut out = a.to_vec();
+    xor_in_place(&mut out, b);
+    out
+}
 
-pub trait CollectRev: Iterator {
-    fn collect_rev<B>(self) -> B
-    where
-        B: FromIteratorRev<Self::Item>,
-        Self: Sized,
-    {
-        B::from_iter_rev(self)
-    }
+/**
+ * Convert bytes to little-endian words, the packing used for keys and blocks by default
+ *
+ * Panics if the number of bytes is not a multiple of the word size.
+ */
+pub fn bytes_to_words<W: Word>(bytes: &[u8]) -> Vec<W> {
+    assert_eq!(bytes.len() % W::BYTES, 0);
+    bytes
+        .chunks_exact(W::BYTES)
+        .map(|chunk| ByteOrder::LittleEndian.read_word(chunk))
+        .collect()
 }
 
-impl<I: Iterator> CollectRev for I {}
+/**
+ * Read a word from at most `BYTES` bytes as if they were followed by zero bytes
+ *
+ * For little-endian order the missing high bytes are zero, for big-endian order the low ones.
+ */
+pub fn partial_word<W: Word>(bytes: &[u8], byte_order: ByteOrder) -> W {
+    // A block is large enough to hold any word
+    let mut buf = W::Block::default();
+    buf.as_mut()[..bytes.len()].copy_from_slice(bytes);
+    byte_order.read_word(&buf.as_ref()[..W::BYTES])
+}
 
-pub trait FromIteratorRev<T> {
-    fn from_iter_rev(iter: impl IntoIterator<Item = T>) -> Self;
+/**
+ * Convert words to their little-endian bytes, see `bytes_to_words`
+ */
+pub fn words_to_bytes<W: Word>(words: &[W]) -> Vec<u8> {
+    let mut bytes = vec![0; words.len() * W::BYTES];
+    for (chunk, word) in bytes.chunks_exact_mut(W::BYTES).zip(words.iter()) {
+        ByteOrder::LittleEndian.write_word(*word, chunk);
+    }
+    bytes
 }
 
-impl<T> FromIteratorRev<T> for Vec<T> {
-    fn from_iter_rev(iter: impl IntoIterator<Item = T>) -> Self {
-        let mut v: Self = iter.into_iter().collect();
-        v.reverse();
-        v
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xor_slices() {
+        let mut dst = [0x0F, 0xF0, 0xAA];
+        xor_in_place(&mut dst, &[0xFF, 0xFF, 0xAA]);
+        assert_eq!(dst, [0xF0, 0x0F, 0x00]);
+
+        assert_eq!(xor(&[1, 2], &[3, 4]), vec![2, 6]);
+        assert!(xor(&[], &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "XOR of slices of different lengths")]
+    fn xor_length_mismatch() {
+        xor_in_place(&mut [0; 8], &[0; 7]);
+    }
+
+    #[test]
+    fn wrapping_arith() {
+        assert_eq!(ArithExt::wrapping_add(u16::MAX, 2), 1);
+        assert_eq!(ArithExt::wrapping_sub(0u32, 1), u32::MAX);
+        assert_eq!(ArithExt::wrapping_mul(u64::MAX, u64::MAX), 1);
+        assert_eq!(ArithExt::wrapping_mul(0x8000u16, 2), 0);
+    }
+
+    #[test]
+    fn ct_eq_results() {
+        assert!(ct_eq(&[], &[]));
+        assert!(ct_eq(&[1, 2, 3, 4, 5, 6, 7, 8], &[1, 2, 3, 4, 5, 6, 7, 8]));
+        assert!(!ct_eq(&[1, 2, 3, 4, 5, 6, 7, 8], &[1, 2, 3, 4, 5, 6, 7, 9]));
+        assert!(!ct_eq(&[0x80, 2, 3], &[0, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn bytes_to_words_le() {
+        let bytes = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        assert_eq!(bytes_to_words::<u32>(&bytes), vec![0x33221100, 0x77665544]);
+        assert_eq!(
+            bytes_to_words::<u16>(&bytes),
+            vec![0x1100, 0x3322, 0x5544, 0x7766]
+        );
+        assert_eq!(bytes_to_words::<u64>(&bytes), vec![0x7766554433221100]);
+    }
+
+    #[test]
+    fn partial_word_zero_padded() {
+        let le = ByteOrder::LittleEndian;
+        assert_eq!(partial_word::<u32>(&[0x00, 0x11], le), 0x1100);
+        assert_eq!(
+            partial_word::<u32>(&[0x00, 0x11, 0x22, 0x33], le),
+            0x33221100
+        );
+        assert_eq!(partial_word::<u64>(&[0x00, 0x11, 0x22], le), 0x221100);
+        assert_eq!(partial_word::<u16>(&[], le), 0);
+
+        let be = ByteOrder::BigEndian;
+        assert_eq!(partial_word::<u32>(&[0x00, 0x11], be), 0x00110000);
+        assert_eq!(
+            partial_word::<u32>(&[0x00, 0x11, 0x22, 0x33], be),
+            0x00112233
+        );
+    }
+
+    #[test]
+    fn words_to_bytes_le() {
+        let bytes = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+
+        assert_eq!(words_to_bytes(&[0x33221100u32, 0x77665544]), bytes);
+        assert_eq!(words_to_bytes(&[0x7766554433221100u64]), bytes);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bytes_to_words_partial_word() {
+        bytes_to_words::<u32>(&[0; 6]);
     }
 }
@@ -0,0 +1,252 @@
+use core::fmt::Debug;
+use core::ops::BitXor;
+
+use crate::util::ArithExt;
+
+/** Number of words in the key table for the default 12 rounds, i.e. 2 * (12 + 1) */
+pub(crate) const DEFAULT_KEY_TABLE_WORDS: usize = 26;
+
+/**
+ * Word type the cipher operates on
+ *
+ * RC5-w/r/b is parameterised over the word size w. A block consists of two words, the magic
+ * constants depend on w as well (see section 4.3).
+ */
+pub trait Word:
+    Copy + Debug + Default + Eq + From<u8> + ArithExt<Self> + BitXor<Output = Self> + MaybeZeroize
+{
+    /** Word size in bits */
+    const BITS: usize;
+
+    /** Word size in bytes */
+    const BYTES: usize;
+
+    /** Magic constant P = Odd((e - 2) * 2^w) */
+    const P: Self;
+
+    /** Magic constant Q = Odd((phi - 1) * 2^w) */
+    const Q: Self;
+
+    /** Byte representation of a block, i.e. two words */
+    type Block: Copy + Debug + Default + Eq + AsRef<[u8]> + AsMut<[u8]>;
+
+    /** Rotate left by the lower lg(w) bits of `n`, i.e. by `n` mod w */
+    fn rotate_left(self, n: u32) -> Self;
+
+    /** Rotate right by the lower lg(w) bits of `n`, i.e. by `n` mod w */
+    fn rotate_right(self, n: u32) -> Self;
+
+    /**
+     * Word as a rotation amount for `rotate_left` and `rotate_right`
+     *
+     * Only the lower lg(w) bits are used for rotating, which are preserved by truncating to `u32`.
+     */
+    fn as_shift(self) -> u32;
+
+    /** Read word from exactly `BYTES` little-endian bytes */
+    fn from_le_bytes(bytes: &[u8]) -> Self;
+
+    /** Write word to exactly `BYTES` little-endian bytes */
+    fn write_le_bytes(self, bytes: &mut [u8]);
+
+    /** Read word from exactly `BYTES` big-endian bytes */
+    fn from_be_bytes(bytes: &[u8]) -> Self;
+
+    /** Write word to exactly `BYTES` big-endian bytes */
+    fn write_be_bytes(self, bytes: &mut [u8]);
+}
+
+/**
+ * Requires words to implement `Zeroize` if the `zeroize` feature is enabled
+ */
+#[cfg(feature = "zeroize")]
+pub trait MaybeZeroize: zeroize::Zeroize {}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> MaybeZeroize for T {}
+
+/**
+ * Requires words to implement `Zeroize` if the `zeroize` feature is enabled
+ */
+#[cfg(not(feature = "zeroize"))]
+pub trait MaybeZeroize {}
+
+#[cfg(not(feature = "zeroize"))]
+impl<T> MaybeZeroize for T {}
+
+/**
+ * Magic constants (P, Q) for words of `word_bits` bits, at most 64
+ *
+ * P = Odd((e - 2) * 2^w) and Q = Odd((phi - 1) * 2^w), where Odd(x) is the odd integer nearest to
+ * x and phi is the golden ratio (section 4.3). Rather than hardcoding the published values, both
+ * fractions are derived here in fixed-point arithmetic: e - 2 as the series of 1/k! for k >= 2 and
+ * phi - 1 as the positive root of x^2 + x - 1.
+ */
+pub const fn magic_constants(word_bits: u32) -> (u128, u128) {
+    assert!(
+        word_bits > 0 && word_bits <= 64,
+        "word size must be 1 to 64 bits"
+    );
+
+    // e - 2 with 127 fractional bits. Truncating the terms only affects the lowest bits.
+    let (mut e, mut term, mut k) = (0u128, 1u128 << 126, 3);
+    while term != 0 {
+        e += term;
+        term /= k;
+        k += 1;
+    }
+
+    // phi - 1 with 64 fractional bits, the largest x with x^2 + x <= 1 found bit by bit. Scaled
+    // by 2^128, the condition is x^2 <= (2^64 - x) * 2^64, which does not overflow.
+    let (mut phi, mut bit) = (0u128, 1u128 << 63);
+    while bit != 0 {
+        let x = phi | bit;
+        if x * x <= ((1 << 64) - x) << 64 {
+            phi = x;
+        }
+        bit >>= 1;
+    }
+
+    // The fractions are irrational, so Odd() of their truncation sets the lowest bit
+    ((e >> (127 - word_bits)) | 1, (phi >> (64 - word_bits)) | 1)
+}
+
+/**
+ * Key table for the default number of rounds after step 2 of the key expansion, for words of
+ * `word_bits` bits, at most 64
+ *
+ * The arithmetic progression P + i * Q does not depend on the key, so it can be computed at
+ * compile time. Every word is stored as eight little-endian bytes, of which only the first w / 8
+ * are used.
+ */
+pub(crate) const fn default_key_table(word_bits: u32) -> [[u8; 8]; DEFAULT_KEY_TABLE_WORDS] {
+    let (p, q) = magic_constants(word_bits);
+    let mask = (1u128 << word_bits) - 1;
+
+    let mut table = [[0; 8]; DEFAULT_KEY_TABLE_WORDS];
+    let (mut v, mut i) = (p, 0);
+    while i < DEFAULT_KEY_TABLE_WORDS {
+        table[i] = (v as u64).to_le_bytes();
+        v = (v + q) & mask;
+        i += 1;
+    }
+    table
+}
+
+macro_rules! impl_word {
+    ($t:ty, $block:ty) => {
+        impl Word for $t {
+            const BITS: usize = <$t>::BITS as usize;
+            const BYTES: usize = core::mem::size_of::<$t>();
+            const P: $t = magic_constants(<$t>::BITS).0 as $t;
+            const Q: $t = magic_constants(<$t>::BITS).1 as $t;
+
+            type Block = $block;
+
+            fn rotate_left(self, n: u32) -> $t {
+                <$t>::rotate_left(self, n & (<$t>::BITS - 1))
+            }
+
+            fn rotate_right(self, n: u32) -> $t {
+                <$t>::rotate_right(self, n & (<$t>::BITS - 1))
+            }
+
+            fn as_shift(self) -> u32 {
+                self as u32
+            }
+
+            // Copies the precomputed key table in `expand_key`, which is generic and therefore
+            // instantiated in other crates
+            #[inline]
+            fn from_le_bytes(bytes: &[u8]) -> $t {
+                let mut buf = [0; core::mem::size_of::<$t>()];
+                buf.copy_from_slice(bytes);
+                <$t>::from_le_bytes(buf)
+            }
+
+            fn write_le_bytes(self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&self.to_le_bytes());
+            }
+
+            fn from_be_bytes(bytes: &[u8]) -> $t {
+                let mut buf = [0; core::mem::size_of::<$t>()];
+                buf.copy_from_slice(bytes);
+                <$t>::from_be_bytes(buf)
+            }
+
+            fn write_be_bytes(self, bytes: &mut [u8]) {
+                bytes.copy_from_slice(&self.to_be_bytes());
+            }
+        }
+    };
+}
+
+impl_word!(u16, [u8; 4]);
+impl_word!(u32, [u8; 8]);
+impl_word!(u64, [u8; 16]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magic_constants_match_paper() {
+        assert_eq!(magic_constants(16), (0xb7e1, 0x9e37));
+        assert_eq!(magic_constants(32), (0xb7e15163, 0x9e3779b9));
+        assert_eq!(
+            magic_constants(64),
+            (0xb7e151628aed2a6b, 0x9e3779b97f4a7c15)
+        );
+
+        assert_eq!((u32::P, u32::Q), (0xb7e15163, 0x9e3779b9));
+    }
+
+    #[test]
+    fn default_key_table_progression() {
+        fn check<W: Word>() {
+            let mut v = W::P;
+            for bytes in default_key_table(W::BITS as u32).iter() {
+                assert_eq!(W::from_le_bytes(&bytes[..W::BYTES]), v);
+                v = v.wrapping_add(W::Q);
+            }
+        }
+
+        check::<u16>();
+        check::<u32>();
+        check::<u64>();
+        assert_eq!(default_key_table(32)[25], 0x2b4c3474u64.to_le_bytes());
+    }
+
+    #[test]
+    fn rotate_u16() {
+        assert_eq!(Word::rotate_left(0x8001u16, 1), 0x0003);
+        assert_eq!(Word::rotate_left(0x1234u16, 16), 0x1234);
+        assert_eq!(Word::rotate_left(0x1234u16, 20), 0x2341);
+        assert_eq!(Word::rotate_right(0x1234u16, 16), 0x1234);
+        assert_eq!(Word::rotate_right(0x1234u16, 36), 0x4123);
+    }
+
+    #[test]
+    fn rotate_u32() {
+        assert_eq!(Word::rotate_left(0x80000001u32, 1), 0x00000003);
+        assert_eq!(Word::rotate_left(0x12345678u32, 32), 0x12345678);
+        assert_eq!(Word::rotate_left(0x12345678u32, 36), 0x23456781);
+        assert_eq!(Word::rotate_right(0x12345678u32, 32), 0x12345678);
+        assert_eq!(Word::rotate_right(0x12345678u32, u32::MAX), 0x2468acf0);
+    }
+
+    #[test]
+    fn rotate_u64() {
+        let x = 0x0123456789abcdefu64;
+        assert_eq!(Word::rotate_left(x, 64), x);
+        assert_eq!(Word::rotate_left(x, 68), 0x123456789abcdef0);
+        assert_eq!(Word::rotate_right(x, 64), x);
+        assert_eq!(Word::rotate_right(x, 132), 0xf0123456789abcde);
+    }
+
+    #[test]
+    fn as_shift_preserves_low_bits() {
+        assert_eq!(0xffffu16.as_shift() & 15, 15);
+        assert_eq!(0x1_0000_0025u64.as_shift() & 63, 0x25);
+    }
+}